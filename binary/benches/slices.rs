@@ -0,0 +1,56 @@
+use binary::{Binary, Buffer, BE, I32, I64, LE};
+use std::time::Instant;
+
+const ROUNDS: u32 = 100;
+const LEN: usize = 1 << 16;
+
+/// Encodes and decodes a large run of 32 and 64-bit integers in both byte orders, once element by
+/// element and once through the bulk slice methods, and reports the average time taken for each.
+fn main() {
+    bench::<I32<LE>>("I32<LE>", |v| I32::new(v as i32));
+    bench::<I32<BE>>("I32<BE>", |v| I32::new(v as i32));
+    bench::<I64<LE>>("I64<LE>", |v| I64::new(v as i64));
+    bench::<I64<BE>>("I64<BE>", |v| I64::new(v as i64));
+}
+
+fn bench<B: Binary>(name: &str, new: fn(usize) -> B) {
+    let vals = (0..LEN).map(|v| new(v * 7919)).collect::<Vec<B>>();
+    let mut buffer = Buffer::new(LEN * 8);
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        buffer.reset();
+        for val in vals.iter() {
+            val.serialize(&mut buffer);
+        }
+    }
+    let element_encode = start.elapsed() / ROUNDS;
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        buffer.set_offset(0);
+        for _ in 0..LEN {
+            B::deserialize(&mut buffer).unwrap();
+        }
+    }
+    let element_decode = start.elapsed() / ROUNDS;
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        buffer.reset();
+        B::serialize_slice(&vals, &mut buffer);
+    }
+    let bulk_encode = start.elapsed() / ROUNDS;
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        buffer.set_offset(0);
+        B::deserialize_vec(&mut buffer, LEN).unwrap();
+    }
+    let bulk_decode = start.elapsed() / ROUNDS;
+
+    println!(
+        "{}: element encode {:?}, decode {:?}; bulk encode {:?}, decode {:?}",
+        name, element_encode, element_decode, bulk_encode, bulk_decode
+    );
+}
@@ -0,0 +1,380 @@
+use crate::{
+    ActorRuntimeId, ActorUniqueId, Array, Binary, BlockPos, Bool, Buffer, ByteOrder, CString,
+    Conditional, Counted, Entries, Latin1String, LossyString, MString, Magic, Map, NulString,
+    Optional, PackedArray, Prefix, Presence, RawString, RemBuf, Sequence, SystemAddress, Timestamp,
+    Uuid, Vec2, Vec3, F16, F32, F64, I128, I16, I24, I32, I64, I8, U128, U16, U24, U32, U48, U64,
+    U8, V32, V64, W32, W64,
+};
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use alloc::{string::String, vec::Vec};
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+macro_rules! impl_arbitrary {
+    ($wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>, $ty:ty $(, $map:expr)?) => {
+        impl<'a, $($gen: $gen_constraint),*> Arbitrary<'a> for $wrapper<$($gen),*> {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                let val = <$ty>::arbitrary(u)?;
+                $( let val = $map(val); )?
+                Ok(Self::new(val))
+            }
+        }
+    };
+}
+
+impl_arbitrary!(U8, <>, u8);
+impl_arbitrary!(I8, <>, i8);
+impl_arbitrary!(U16, <E: ByteOrder>, u16);
+impl_arbitrary!(I16, <E: ByteOrder>, i16);
+impl_arbitrary!(U24, <E: ByteOrder>, u32, |v: u32| v & 0xff_ffff);
+impl_arbitrary!(I24, <E: ByteOrder>, i32, |v: i32| v << 8 >> 8);
+impl_arbitrary!(U32, <E: ByteOrder>, u32);
+impl_arbitrary!(I32, <E: ByteOrder>, i32);
+impl_arbitrary!(U48, <E: ByteOrder>, u64, |v: u64| v & 0xffff_ffff_ffff);
+impl_arbitrary!(U64, <E: ByteOrder>, u64);
+impl_arbitrary!(I64, <E: ByteOrder>, i64);
+impl_arbitrary!(U128, <E: ByteOrder>, u128);
+impl_arbitrary!(I128, <E: ByteOrder>, i128);
+impl_arbitrary!(F32, <E: ByteOrder>, f32);
+impl_arbitrary!(F64, <E: ByteOrder>, f64);
+impl_arbitrary!(W32, <>, u32);
+impl_arbitrary!(V32, <>, i32);
+impl_arbitrary!(W64, <>, u64);
+impl_arbitrary!(V64, <>, i64);
+impl_arbitrary!(Bool, <>, bool);
+impl_arbitrary!(ActorRuntimeId, <>, u64);
+impl_arbitrary!(ActorUniqueId, <>, i64);
+impl_arbitrary!(Sequence, <>, u32, |v: u32| v % Sequence::MODULUS);
+impl_arbitrary!(Timestamp, <>, u64);
+
+/// Only floats that are exactly representable as half precision floats are generated.
+impl<'a, E: ByteOrder> Arbitrary<'a> for F16<E> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bytes = <[u8; 2]>::arbitrary(u)?;
+        Ok(Self::deserialize(&mut Buffer::from(&bytes)).unwrap())
+    }
+}
+
+/// Returns an arbitrary string that takes at most max bytes once encoded, where the encoded
+/// length of every character is given by the function.
+fn string(u: &mut Unstructured, max: usize, len: impl Fn(char) -> usize) -> Result<String> {
+    let mut str = String::new();
+    let mut size = 0;
+
+    for c in <&str>::arbitrary(u)?.chars() {
+        size += len(c);
+        if size > max {
+            break;
+        }
+        str.push(c);
+    }
+
+    Ok(str)
+}
+
+impl<'a, P: Prefix> Arbitrary<'a> for CString<P> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(string(u, P::MAX, char::len_utf8)?))
+    }
+}
+
+impl<'a, P: Prefix> Arbitrary<'a> for LossyString<P> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(string(u, P::MAX, char::len_utf8)?))
+    }
+}
+
+impl<'a, P: Prefix> Arbitrary<'a> for MString<P> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = |c: char| match c {
+            '\0' => 2,
+            c if c.len_utf16() == 2 => 6,
+            c => c.len_utf8(),
+        };
+
+        Ok(Self::new(string(u, P::MAX, len)?))
+    }
+}
+
+impl<'a, P: Prefix> Arbitrary<'a> for RawString<P> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bytes = <&[u8]>::arbitrary(u)?;
+        Ok(Self::new(bytes[..bytes.len().min(P::MAX)].to_vec()))
+    }
+}
+
+impl<'a, P: Prefix> Arbitrary<'a> for RemBuf<P> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bytes = <&[u8]>::arbitrary(u)?;
+        Ok(Self::new(bytes[..bytes.len().min(P::MAX)].to_vec()))
+    }
+}
+
+impl<'a, P: Prefix> Arbitrary<'a> for Latin1String<P> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bytes = <&[u8]>::arbitrary(u)?;
+        let str = bytes.iter().take(P::MAX).map(|&b| b as char).collect();
+
+        Ok(Self::new(str))
+    }
+}
+
+impl<'a> Arbitrary<'a> for NulString {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let str = <&str>::arbitrary(u)?.replace('\0', "");
+        Ok(Self::new(str))
+    }
+}
+
+impl<'a, P: Prefix, B: Binary + Arbitrary<'a>> Arbitrary<'a> for Array<P, B> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = u.arbitrary_len::<B>()?.min(P::MAX);
+        let vec = (0..len).map(|_| B::arbitrary(u)).collect::<Result<_>>()?;
+
+        Ok(Self::new(vec))
+    }
+}
+
+impl<'a, B: Binary + Arbitrary<'a>> Arbitrary<'a> for Counted<B> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(Vec::arbitrary(u)?))
+    }
+}
+
+impl<'a, M: Presence, B: Binary + Arbitrary<'a>> Arbitrary<'a> for Optional<M, B> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(Option::arbitrary(u)?))
+    }
+}
+
+impl<'a, B: Binary + Arbitrary<'a>> Arbitrary<'a> for Conditional<B> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(Option::arbitrary(u)?))
+    }
+}
+
+/// Entries with a key that was already generated are dropped, as the map could not be read back.
+impl<'a, P: Prefix, M: Entries> Arbitrary<'a> for Map<P, M>
+where
+    M::Key: Arbitrary<'a>,
+    M::Value: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = u.arbitrary_len::<(M::Key, M::Value)>()?.min(P::MAX);
+        let mut map = M::default();
+
+        for _ in 0..len {
+            map.insert_unique(M::Key::arbitrary(u)?, M::Value::arbitrary(u)?);
+        }
+
+        Ok(Self::new(map))
+    }
+}
+
+/// Every value is generated within the width, as the bits above it are not written, and arrays
+/// of zero bits hold at most [`PackedArray::MAX_ZEROS`] values.
+impl<'a, E: ByteOrder> Arbitrary<'a> for PackedArray<E> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bits = u.int_in_range(0..=32)?;
+        let mask = u32::MAX.checked_shr(32 - bits as u32).unwrap_or(0);
+        let len = u.arbitrary_len::<u32>()?.min(Self::MAX_ZEROS);
+        let values = (0..len)
+            .map(|_| Ok(u32::arbitrary(u)? & mask))
+            .collect::<Result<_>>()?;
+
+        Ok(Self::new(bits, values).unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for BlockPos {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::from(<[i32; 3]>::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Vec3 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::from(<[f32; 3]>::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Vec2 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::from(<[f32; 2]>::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Uuid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(u128::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Magic {
+    fn arbitrary(_: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Magic)
+    }
+}
+
+impl<'a> Arbitrary<'a> for SystemAddress {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let port = u16::arbitrary(u)?;
+        let addr = match bool::arbitrary(u)? {
+            true => {
+                let ip = Ipv4Addr::from(<[u8; 4]>::arbitrary(u)?);
+                SocketAddr::V4(SocketAddrV4::new(ip, port))
+            }
+            false => {
+                let ip = Ipv6Addr::from(<[u8; 16]>::arbitrary(u)?);
+                let (flowinfo, scope_id) = <(u32, u32)>::arbitrary(u)?;
+                SocketAddr::V6(SocketAddrV6::new(ip, port, flowinfo, scope_id))
+            }
+        };
+
+        Ok(Self::new(addr))
+    }
+}
+
+/// The wrappers of the [`crate::endian`] module share their names with the statically ordered
+/// wrappers, so they are implemented in a module of their own.
+mod endian {
+    use super::{Arbitrary, Result, Unstructured};
+    use crate::endian::{F16, F32, F64, I128, I16, I24, I32, I64, U128, U16, U24, U32, U48, U64};
+    use crate::{Buffer, Endian, EndianBinary};
+
+    impl_arbitrary!(U16, <>, u16);
+    impl_arbitrary!(I16, <>, i16);
+    impl_arbitrary!(U24, <>, u32, |v: u32| v & 0xff_ffff);
+    impl_arbitrary!(I24, <>, i32, |v: i32| v << 8 >> 8);
+    impl_arbitrary!(U32, <>, u32);
+    impl_arbitrary!(I32, <>, i32);
+    impl_arbitrary!(U48, <>, u64, |v: u64| v & 0xffff_ffff_ffff);
+    impl_arbitrary!(U64, <>, u64);
+    impl_arbitrary!(I64, <>, i64);
+    impl_arbitrary!(U128, <>, u128);
+    impl_arbitrary!(I128, <>, i128);
+    impl_arbitrary!(F32, <>, f32);
+    impl_arbitrary!(F64, <>, f64);
+
+    /// Only floats that are exactly representable as half precision floats are generated.
+    impl<'a> Arbitrary<'a> for F16 {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let bytes = <[u8; 2]>::arbitrary(u)?;
+            let mut buf = Buffer::from(&bytes);
+            Ok(Self::deserialize_endian(Endian::Little, &mut buf).unwrap())
+        }
+    }
+}
+
+mod tests {
+    ///
+    /// Checks that generated values serialize to bytes that read back into the same bytes, in
+    /// either byte order for the wrappers whose byte order is only known at runtime.
+    ///
+    #[test]
+    pub fn arbitrary() {
+        use crate::*;
+        use ::arbitrary::{Arbitrary, Unstructured};
+        use std::collections::BTreeMap;
+
+        fn round_trip<T: Binary + for<'a> Arbitrary<'a>>(data: &[u8]) {
+            let mut u = Unstructured::new(data);
+            while !u.is_empty() {
+                let val = T::arbitrary(&mut u).unwrap();
+                let mut buf = Buffer::new(1 << 16);
+                val.serialize(&mut buf);
+                let len = buf.offset();
+
+                buf.set_offset(0);
+                let read = T::deserialize(&mut buf).unwrap();
+                assert_eq!(buf.offset(), len, "{:?}", val);
+
+                let mut again = Buffer::new(len);
+                read.serialize(&mut again);
+                assert_eq!(&again[..], &buf[..len]);
+            }
+        }
+
+        fn round_trip_endian<T: EndianBinary + for<'a> Arbitrary<'a>>(data: &[u8]) {
+            for endian in [Endian::Little, Endian::Big] {
+                let mut u = Unstructured::new(data);
+                while !u.is_empty() {
+                    let val = T::arbitrary(&mut u).unwrap();
+                    let mut buf = Buffer::new(16);
+                    val.serialize_endian(endian, &mut buf);
+                    let len = buf.offset();
+
+                    buf.set_offset(0);
+                    let read = T::deserialize_endian(endian, &mut buf).unwrap();
+                    assert_eq!(buf.offset(), len, "{:?}", val);
+
+                    let mut again = Buffer::new(len);
+                    read.serialize_endian(endian, &mut again);
+                    assert_eq!(&again[..], &buf[..len]);
+                }
+            }
+        }
+
+        fn round_trip_packed<E: ByteOrder>(data: &[u8]) {
+            let mut u = Unstructured::new(data);
+            while !u.is_empty() {
+                let val = PackedArray::<E>::arbitrary(&mut u).unwrap();
+                let mut buf = Buffer::new(1 << 16);
+                val.serialize(&mut buf);
+                let len = buf.offset();
+
+                buf.set_offset(0);
+                let read = PackedArray::<E>::deserialize(&mut buf, val.bits(), val.values().len());
+                assert_eq!(buf.offset(), len, "{:?}", val.values());
+                assert_eq!(read.unwrap().values(), val.values());
+            }
+        }
+
+        binary_enum! {
+            enum GameMode: V32 {
+                Survival = 0,
+            }
+        }
+
+        flags! {
+            struct Flags: U16<LE> {
+                FIRST = 0,
+            }
+        }
+
+        let data: Vec<u8> = (0..8192u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect();
+        round_trip::<(U24<LE>, I24<BE>, U48<LE>, F16<BE>, W32, V64, Sequence)>(&data);
+        round_trip::<(
+            CString<U16<LE>>,
+            MString<U16<BE>>,
+            Latin1String<W32>,
+            NulString,
+        )>(&data);
+        round_trip::<(RawString<I16<LE>>, Array<W32, (Bool, Vec3, BlockPos)>, Uuid)>(&data);
+        round_trip::<(
+            Map<V32, BTreeMap<CString<W32>, I64<LE>>>,
+            Optional<Bool, F32<LE>>,
+        )>(&data);
+        round_trip::<(Magic, SystemAddress, Timestamp, GameMode, Flags)>(&data);
+        round_trip::<(RemBuf<U16<LE>>, RemBuf<W32>)>(&data);
+
+        round_trip_packed::<LE>(&data);
+        round_trip_packed::<BE>(&data);
+
+        round_trip_endian::<endian::U16>(&data);
+        round_trip_endian::<endian::I16>(&data);
+        round_trip_endian::<endian::U24>(&data);
+        round_trip_endian::<endian::I24>(&data);
+        round_trip_endian::<endian::U32>(&data);
+        round_trip_endian::<endian::I32>(&data);
+        round_trip_endian::<endian::U48>(&data);
+        round_trip_endian::<endian::U64>(&data);
+        round_trip_endian::<endian::I64>(&data);
+        round_trip_endian::<endian::U128>(&data);
+        round_trip_endian::<endian::I128>(&data);
+        round_trip_endian::<endian::F16>(&data);
+        round_trip_endian::<endian::F32>(&data);
+        round_trip_endian::<endian::F64>(&data);
+    }
+}
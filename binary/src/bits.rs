@@ -0,0 +1,283 @@
+use crate::{Buffer, ByteOrder};
+use alloc::{vec, vec::Vec};
+use core::marker::PhantomData;
+
+/// BitReader reads values of 1 to 32 bits that are packed into 32-bit words, starting from the
+/// least significant bit of each word. Values never span two words, if the current word does not
+/// have enough bits left for a value then the remaining bits are skipped as padding and the value
+/// is read from the next word. This is the layout used by the block storages of sub chunks.
+pub struct BitReader<'a, 'b, E: ByteOrder> {
+    buf: &'a mut Buffer<'b>,
+    word: u32,
+    left: u32,
+    _marker: PhantomData<E>,
+}
+
+impl<'a, 'b, E: ByteOrder> BitReader<'a, 'b, E> {
+    /// Creates and returns a new BitReader reading words from the provided buffer.
+    pub fn new(buf: &'a mut Buffer<'b>) -> Self {
+        Self {
+            buf,
+            word: 0,
+            left: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reads a value of the specified number of bits. Returns None if the width is not between 1
+    /// and 32 bits or if the buffer does not have enough bytes left for the next word.
+    pub fn read(&mut self, bits: u32) -> Option<u32> {
+        if !(1..=32).contains(&bits) {
+            return None;
+        }
+
+        if self.left < bits {
+            self.word = E::read_u32(self.buf)?;
+            self.left = 32;
+        }
+
+        let val = self.word & mask(bits);
+        self.word = self.word.checked_shr(bits).unwrap_or(0);
+        self.left -= bits;
+
+        Some(val)
+    }
+}
+
+/// BitWriter writes values of 1 to 32 bits packed into 32-bit words using the same layout that is
+/// read by [`BitReader`]. The last word is written once the writer is finished or dropped.
+pub struct BitWriter<'a, 'b, E: ByteOrder> {
+    buf: &'a mut Buffer<'b>,
+    word: u32,
+    used: u32,
+    _marker: PhantomData<E>,
+}
+
+impl<'a, 'b, E: ByteOrder> BitWriter<'a, 'b, E> {
+    /// Creates and returns a new BitWriter writing words into the provided buffer.
+    pub fn new(buf: &'a mut Buffer<'b>) -> Self {
+        Self {
+            buf,
+            word: 0,
+            used: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writes the lowest bits of the value. The current word is padded and written if it does not
+    /// have enough space left for the value. Returns None and writes nothing if the width is not
+    /// between 1 and 32 bits.
+    pub fn write(&mut self, val: u32, bits: u32) -> Option<()> {
+        if !(1..=32).contains(&bits) {
+            return None;
+        }
+
+        if self.used + bits > 32 {
+            self.flush();
+        }
+
+        self.word |= (val & mask(bits)) << self.used;
+        self.used += bits;
+        Some(())
+    }
+
+    /// Writes the last partially filled word if there is one.
+    pub fn finish(mut self) {
+        self.flush();
+    }
+
+    fn flush(&mut self) {
+        if self.used > 0 {
+            E::write_u32(self.word, self.buf);
+            self.word = 0;
+            self.used = 0;
+        }
+    }
+}
+
+impl<E: ByteOrder> Drop for BitWriter<'_, '_, E> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[inline]
+fn mask(bits: u32) -> u32 {
+    u32::MAX.checked_shr(32 - bits).unwrap_or(0)
+}
+
+/// PackedArray represents an array of unsigned values of a fixed number of bits per value which
+/// are packed into 32-bit words of the byte order E. A width of zero bits is valid and encodes
+/// an array of zeros in no words at all, which is used for single entry palettes.
+#[derive(Debug)]
+pub struct PackedArray<E: ByteOrder> {
+    bits: u8,
+    values: Vec<u32>,
+    _marker: PhantomData<E>,
+}
+
+impl<E: ByteOrder> PackedArray<E> {
+    /// The largest number of values deserialized for a width of zero bits. Such an array takes up
+    /// no bytes, so its length is not bounded by the buffer and is capped instead. This is large
+    /// enough for the block and biome storages of a sub chunk.
+    pub const MAX_ZEROS: usize = 1 << 16;
+
+    /// Creates and returns a new PackedArray of the values with the specified bits per value. Any
+    /// bits of the values above the width are discarded while serializing. Returns None if the
+    /// width exceeds the 32 bits of a word.
+    pub fn new(bits: u8, values: Vec<u32>) -> Option<Self> {
+        if bits > 32 {
+            return None;
+        }
+
+        Some(Self {
+            bits,
+            values,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the number of bits per value.
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Returns the values of the array.
+    pub fn values(&self) -> &[u32] {
+        &self.values
+    }
+
+    /// Returns the values of the array by consuming it.
+    pub fn into_values(self) -> Vec<u32> {
+        self.values
+    }
+
+    /// Returns the number of words needed to pack len values of the specified bits per value.
+    /// Returns None if the width exceeds the 32 bits of a word.
+    pub fn words(bits: u8, len: usize) -> Option<usize> {
+        match bits {
+            0 => Some(0),
+            1..=32 => Some(len.div_ceil(32 / bits as usize)),
+            _ => None,
+        }
+    }
+
+    /// Serializes the values packed into words.
+    pub fn serialize(&self, buf: &mut Buffer) {
+        if self.bits == 0 {
+            return;
+        }
+
+        // The width is known to be valid as it is checked when the array is created.
+        let mut writer = BitWriter::<E>::new(buf);
+        for val in self.values.iter() {
+            _ = writer.write(*val, self.bits as u32);
+        }
+
+        writer.finish();
+    }
+
+    /// Deserializes len values of the specified bits per value. Both the width and the number of
+    /// values are expected to be known from earlier fields. Returns None if the buffer does not
+    /// hold enough words, or if the width is zero and len exceeds [`PackedArray::MAX_ZEROS`].
+    pub fn deserialize(buf: &mut Buffer, bits: u8, len: usize) -> Option<Self> {
+        if buf.remaining() / 4 < Self::words(bits, len)? {
+            return None;
+        }
+
+        if bits == 0 {
+            if len > Self::MAX_ZEROS {
+                return None;
+            }
+
+            return Self::new(bits, vec![0; len]);
+        }
+
+        let mut reader = BitReader::<E>::new(buf);
+        let mut values = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            values.push(reader.read(bits as u32)?);
+        }
+
+        Self::new(bits, values)
+    }
+}
+
+mod tests {
+    ///
+    /// Tests packing values of a width that does not divide a word, where the two most
+    /// significant bits of every word are left as padding.
+    ///
+    #[test]
+    pub fn packed() {
+        use crate::{Buffer, PackedArray, LE};
+
+        let values = (0..20).map(|i| i % 8).collect::<Vec<u32>>();
+        assert_eq!(PackedArray::<LE>::words(3, values.len()), Some(2));
+        assert_eq!(PackedArray::<LE>::words(33, values.len()), None);
+        assert!(PackedArray::<LE>::new(33, values.clone()).is_none());
+
+        let mut buffer = Buffer::new(8);
+        let array = PackedArray::<LE>::new(3, values.clone()).unwrap();
+        array.serialize(&mut buffer);
+        assert_eq!(buffer.offset(), 8);
+
+        let first = u32::from_le_bytes(buffer[..4].try_into().unwrap());
+        assert_eq!(first >> 30, 0);
+        assert_eq!(first & 0b111_111, 0b001_000);
+
+        buffer.set_offset(0);
+        let array = PackedArray::<LE>::deserialize(&mut buffer, 3, values.len()).unwrap();
+        assert_eq!(array.values(), &values[..]);
+    }
+
+    ///
+    /// Tests that the words are written in big endian order with the values packed from the
+    /// least significant bit of every word.
+    ///
+    #[test]
+    pub fn packed_be() {
+        use crate::{Buffer, PackedArray, BE};
+
+        let mut buffer = Buffer::new(8);
+        let array = PackedArray::<BE>::new(16, vec![0x0102, 0x0304, 0x0506]).unwrap();
+        array.serialize(&mut buffer);
+        assert_eq!(
+            &buffer[..],
+            &[0x03, 0x04, 0x01, 0x02, 0x00, 0x00, 0x05, 0x06]
+        );
+
+        buffer.set_offset(0);
+        let array = PackedArray::<BE>::deserialize(&mut buffer, 16, 3).unwrap();
+        assert_eq!(array.values(), &[0x0102, 0x0304, 0x0506]);
+
+        buffer.set_offset(0);
+        assert!(PackedArray::<BE>::deserialize(&mut buffer, 16, 5).is_none());
+        assert!(PackedArray::<BE>::deserialize(&mut buffer, 33, 1).is_none());
+
+        let zeros = PackedArray::<BE>::deserialize(&mut buffer, 0, PackedArray::<BE>::MAX_ZEROS);
+        assert_eq!(zeros.unwrap().values().len(), 1 << 16);
+        assert!(PackedArray::<BE>::deserialize(&mut buffer, 0, usize::MAX).is_none());
+    }
+
+    ///
+    /// Tests that widths outside of 1 to 32 bits are rejected by the reader and the writer.
+    ///
+    #[test]
+    pub fn widths() {
+        use crate::{BitReader, BitWriter, Buffer, LE};
+
+        let mut buffer = Buffer::new(4);
+        let mut writer = BitWriter::<LE>::new(&mut buffer);
+        assert!(writer.write(1, 0).is_none());
+        assert!(writer.write(1, 33).is_none());
+        assert!(writer.write(u32::MAX, 32).is_some());
+        writer.finish();
+
+        buffer.set_offset(0);
+        let mut reader = BitReader::<LE>::new(&mut buffer);
+        assert!(reader.read(33).is_none());
+        assert_eq!(reader.read(32), Some(u32::MAX));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{Binary, Buffer, Prefix};
+use alloc::vec;
+use bytes::{Buf, BufMut};
+
+/// The number of bytes a length prefix is decoded from by [`BinaryBuf::read_prefixed`], which is
+/// more than any [`Prefix`] takes up.
+const HEADER: usize = 16;
+
+/// BinaryBuf is implemented for every [`Buf`] that can be cloned and allows deserializing
+/// [`Binary`] objects directly from them.
+pub trait BinaryBuf: Buf + Clone {
+    /// Deserializes an object from the next len bytes of the buffer, which are expected to hold
+    /// exactly one object such as a frame whose length is known from an earlier prefix. Nothing is
+    /// copied if the bytes lie within the current chunk, otherwise they are gathered into a
+    /// temporary vector once. Returns None if fewer than len bytes remain, or if the object cannot
+    /// be read from them or leaves some of them unread. The buffer is not advanced on failure.
+    fn read_binary<B: Binary>(&mut self, len: usize) -> Option<B> {
+        if self.remaining() < len {
+            return None;
+        }
+
+        let val = if self.chunk().len() >= len {
+            exact(&self.chunk()[..len])?
+        } else {
+            let mut vec = vec![0u8; len];
+            self.clone().copy_to_slice(&mut vec);
+            exact(&vec)?
+        };
+
+        self.advance(len);
+        Some(val)
+    }
+
+    /// Deserializes an object framed by a length prefix P, see [`BinaryBuf::read_binary`]. The
+    /// buffer is not advanced on failure.
+    fn read_prefixed<P: Prefix, B: Binary>(&mut self) -> Option<B> {
+        let mut header = [0u8; HEADER];
+        let n = self.remaining().min(HEADER);
+        self.clone().copy_to_slice(&mut header[..n]);
+
+        let mut buf = Buffer::from(&header[..n]);
+        let len = P::decode(&mut buf)?;
+
+        let mut peek = self.clone();
+        peek.advance(buf.offset());
+        let val = peek.read_binary(len)?;
+
+        *self = peek;
+        Some(val)
+    }
+}
+
+impl<T: Buf + Clone> BinaryBuf for T {}
+
+/// Deserializes an object that is expected to take up all of the bytes.
+fn exact<B: Binary>(bytes: &[u8]) -> Option<B> {
+    let mut buf = Buffer::from(bytes);
+    let val = B::deserialize(&mut buf)?;
+
+    (buf.remaining() == 0).then_some(val)
+}
+
+/// The number of bytes past the limit an object is serialized with by
+/// [`BinaryBufMut::write_binary`]. A write that runs out of space leaves fewer bytes unwritten
+/// than the widest value written at once, so an object that does not fit always ends up within
+/// this many bytes of the end of the temporary buffer.
+const SLACK: usize = 16;
+
+/// The size of the temporary buffer objects are first serialized into by
+/// [`BinaryBufMut::write_binary`].
+const INITIAL: usize = 64;
+
+/// BinaryBufMut is implemented for every [`BufMut`] and allows serializing [`Binary`] objects
+/// directly into them.
+pub trait BinaryBufMut: BufMut {
+    /// Serializes the object into the buffer and returns the number of bytes written. Returns
+    /// None and writes nothing if the object does not fit within cap bytes. The object is
+    /// serialized into a temporary buffer which starts small and doubles until the object fits,
+    /// so the cost depends on the size of the object rather than on cap. Only the bytes written
+    /// are copied into the buffer.
+    fn write_binary<B: Binary>(&mut self, val: &B, cap: usize) -> Option<usize> {
+        let limit = cap.saturating_add(SLACK);
+        let mut len = limit.min(INITIAL);
+
+        loop {
+            let mut buf = Buffer::new(len);
+            val.serialize(&mut buf);
+
+            let n = buf.offset();
+            if n > cap {
+                return None;
+            }
+
+            if n <= len - SLACK || len == limit {
+                self.put_slice(buf.as_ref());
+                return Some(n);
+            }
+
+            len = len.saturating_mul(2).min(limit);
+        }
+    }
+}
+
+impl<T: BufMut + ?Sized> BinaryBufMut for T {}
+
+mod tests {
+    ///
+    /// Tests writing objects into a BytesMut and reading them back from the frozen bytes.
+    ///
+    #[test]
+    pub fn round_trip() {
+        use crate::{Array, BinaryBuf, BinaryBufMut, CString, LE, U32, U8, W32};
+        use bytes::BytesMut;
+
+        let mut bytes = BytesMut::new();
+        bytes.write_binary(&W32::new(300), 5).unwrap();
+        bytes
+            .write_binary(&CString::<W32>::new("Hello".to_string()), 16)
+            .unwrap();
+        bytes.write_binary(&U32::<LE>::new(7), 4).unwrap();
+
+        let array = Array::<W32, U8>::new(vec![U8::new(1); 200]);
+        assert_eq!(bytes.write_binary(&array, usize::MAX), Some(202));
+
+        let mut frozen = bytes.freeze();
+        assert_eq!(frozen.read_binary::<W32>(2).unwrap().get(), 300);
+        assert!(frozen.read_binary::<CString<W32>>(7).is_none());
+
+        let str = frozen.read_binary::<CString<W32>>(6).unwrap();
+        assert_eq!(str.get(), "Hello");
+        assert_eq!(frozen.read_binary::<U32<LE>>(4).unwrap().get(), 7);
+        assert_eq!(frozen.read_binary::<Array<W32, U8>>(202), Some(array));
+        assert!(frozen.read_binary::<U32<LE>>(4).is_none());
+    }
+
+    ///
+    /// Tests reading objects spanning several chunks of a buffer that only exposes its current
+    /// chunk, and that objects exceeding the limit are not written.
+    ///
+    #[test]
+    pub fn chunks() {
+        use crate::{Array, BinaryBuf, BinaryBufMut, CString, Counted, U8, W32};
+        use bytes::Buf;
+
+        #[derive(Clone)]
+        struct Chunks(Vec<Vec<u8>>);
+
+        impl Buf for Chunks {
+            fn remaining(&self) -> usize {
+                self.0.iter().map(Vec::len).sum()
+            }
+
+            fn chunk(&self) -> &[u8] {
+                self.0.first().map(Vec::as_slice).unwrap_or(&[])
+            }
+
+            fn advance(&mut self, mut cnt: usize) {
+                while cnt > 0 {
+                    let n = cnt.min(self.0[0].len());
+                    self.0[0].drain(..n);
+                    if self.0[0].is_empty() {
+                        self.0.remove(0);
+                    }
+                    cnt -= n;
+                }
+            }
+        }
+
+        let mut chunks = Chunks(vec![vec![5, b'H', b'e'], vec![b'l', b'l'], vec![b'o', 1]]);
+        let str = chunks.read_binary::<CString<W32>>(6).unwrap();
+        assert_eq!(str.get(), "Hello");
+        assert!(chunks.read_binary::<CString<W32>>(1).is_none());
+        assert_eq!(chunks.read_binary::<U8>(1).unwrap().get(), 1);
+
+        let mut chunks = Chunks(vec![vec![3, 1], vec![2], vec![3, 9]]);
+        let counted = chunks.read_prefixed::<W32, Counted<U8>>().unwrap();
+        assert_eq!(counted.len(), 3);
+        assert_eq!(chunks.remaining(), 1);
+
+        let array = Array::<W32, U8>::new(vec![U8::new(0); 20]);
+        let mut vec = Vec::new();
+        assert!(vec.write_binary(&array, 20).is_none());
+        assert!(vec.is_empty());
+        assert_eq!(vec.write_binary(&array, 21), Some(21));
+    }
+}
@@ -1,23 +1,64 @@
-use std::ops::{Deref, DerefMut};
+#[cfg(feature = "trace")]
+use crate::Trace;
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
 
 /// Buffer represents a fast implementation of zero copy and non growable buffer. It can be
 /// internally resized however it does not affect the original length of the vector this buffer
-/// allocates.
-pub struct Buffer {
-    slice: Vec<u8>,
+/// allocates. A buffer either owns its bytes or borrows them from a slice, in which case no
+/// allocation takes place at all.
+pub struct Buffer<'a> {
+    slice: Storage<'a>,
     offset: usize,
     size: usize,
     cap: usize,
+    #[cfg(feature = "trace")]
+    trace: Option<Trace>,
 }
 
-impl Buffer {
+/// Storage represents the bytes backing a [`Buffer`].
+enum Storage<'a> {
+    Owned(Vec<u8>),
+    Borrowed(&'a [u8]),
+    BorrowedMut(&'a mut [u8]),
+    #[cfg(feature = "bytes")]
+    Shared(bytes::Bytes),
+}
+
+impl Storage<'_> {
+    #[inline]
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Storage::Owned(v) => v,
+            Storage::Borrowed(v) => v,
+            Storage::BorrowedMut(v) => v,
+            #[cfg(feature = "bytes")]
+            Storage::Shared(v) => v,
+        }
+    }
+
+    #[inline]
+    fn bytes_mut(&mut self) -> Option<&mut [u8]> {
+        match self {
+            Storage::Owned(v) => Some(v),
+            Storage::Borrowed(_) => None,
+            Storage::BorrowedMut(v) => Some(v),
+            #[cfg(feature = "bytes")]
+            Storage::Shared(_) => None,
+        }
+    }
+}
+
+impl<'a> Buffer<'a> {
     /// Creates and returns a new Buffer of the specified capacity
     pub fn new(cap: usize) -> Self {
         Self {
-            slice: vec![0u8; cap],
+            slice: Storage::Owned(vec![0u8; cap]),
             offset: 0,
             size: cap,
-            cap: cap,
+            cap,
+            #[cfg(feature = "trace")]
+            trace: None,
         }
     }
 
@@ -42,6 +83,12 @@ impl Buffer {
         self.cap
     }
 
+    /// Returns true if the buffer is backed by an immutable slice, in which case nothing can be
+    /// written into it.
+    pub fn is_read_only(&self) -> bool {
+        !matches!(self.slice, Storage::Owned(_) | Storage::BorrowedMut(_))
+    }
+
     /// Advances the buffer by the specified capacity. Returns true if the operation was
     /// successful and returns false if the buffer did not have enough space.
     pub fn advance(&mut self, offset: usize) -> bool {
@@ -73,7 +120,7 @@ impl Buffer {
             self.offset += n;
         }
 
-        &self.slice[start..end]
+        &self.slice.bytes()[..self.size][start..end]
     }
 
     /// Reads into the provided mutable slice. If the length of the provided slice exceeds the
@@ -91,8 +138,8 @@ impl Buffer {
         let end = self.offset + size;
 
         unsafe {
-            std::ptr::copy_nonoverlapping(
-                self.slice[start..end].as_ptr(),
+            core::ptr::copy_nonoverlapping(
+                self.slice.bytes()[start..end].as_ptr(),
                 buf[..size].as_mut_ptr(),
                 size,
             )
@@ -104,6 +151,7 @@ impl Buffer {
 
     /// Writes into the buffer from the provided slice. If the length of the bytes to be written exceeds the
     /// amount of space available in the buffer then it writes as many bytes it can and returns the number.
+    /// Nothing is written if the buffer is read only.
     pub fn write(&mut self, buf: &[u8]) -> usize {
         let remaining = self.remaining();
         let size = buf.len().min(remaining);
@@ -115,10 +163,15 @@ impl Buffer {
         let start = self.offset;
         let end = self.offset + size;
 
+        let slice = match self.slice.bytes_mut() {
+            Some(slice) => slice,
+            None => return 0,
+        };
+
         unsafe {
-            std::ptr::copy_nonoverlapping(
+            core::ptr::copy_nonoverlapping(
                 buf[..size].as_ptr(),
-                self.slice[start..end].as_mut_ptr(),
+                slice[start..end].as_mut_ptr(),
                 size,
             )
         }
@@ -127,6 +180,128 @@ impl Buffer {
         size
     }
 
+    /// Returns a bounded view over the next n bytes of the buffer. The view is a buffer of its own
+    /// over just those n bytes, so nothing done through it can reach past them, and the buffer is
+    /// advanced by exactly n bytes regardless of how many bytes the view consumed. Returns None if
+    /// fewer than n bytes remain.
+    pub fn take(&mut self, n: usize) -> Option<Take<'_>> {
+        if self.remaining() < n {
+            return None;
+        }
+
+        let start = self.offset;
+        let end = self.offset + n;
+        self.offset = end;
+
+        let slice = match &mut self.slice {
+            Storage::Owned(v) => Storage::BorrowedMut(&mut v[start..end]),
+            Storage::Borrowed(v) => Storage::Borrowed(&v[start..end]),
+            Storage::BorrowedMut(v) => Storage::BorrowedMut(&mut v[start..end]),
+            #[cfg(feature = "bytes")]
+            Storage::Shared(v) => Storage::Borrowed(&v[start..end]),
+        };
+
+        #[cfg(feature = "trace")]
+        let mut trace = self.trace.take();
+        #[cfg(feature = "trace")]
+        if let Some(trace) = trace.as_mut() {
+            trace.origin += start;
+        }
+
+        Some(Take {
+            view: Buffer {
+                slice,
+                offset: 0,
+                size: n,
+                cap: n,
+                #[cfg(feature = "trace")]
+                trace,
+            },
+            #[cfg(feature = "trace")]
+            trace: &mut self.trace,
+            #[cfg(feature = "trace")]
+            start,
+        })
+    }
+
+    /// Converts the bytes written so far into [`bytes::Bytes`]. This does not copy if the buffer
+    /// owns its bytes or was created from [`bytes::Bytes`] in the first place.
+    #[cfg(feature = "bytes")]
+    pub fn freeze(self) -> bytes::Bytes {
+        match self.slice {
+            Storage::Owned(mut v) => {
+                v.truncate(self.offset);
+                bytes::Bytes::from(v)
+            }
+            Storage::Shared(v) => v.slice(..self.offset),
+            _ => bytes::Bytes::copy_from_slice(&self.slice.bytes()[..self.offset]),
+        }
+    }
+
+    /// Returns the bytes of the buffer up to its size as a mutable slice. Returns None if the
+    /// buffer is read only, unlike mutably dereferencing the buffer which panics in that case.
+    pub fn as_mut_slice(&mut self) -> Option<&mut [u8]> {
+        let size = self.size;
+        self.slice.bytes_mut().map(|slice| &mut slice[..size])
+    }
+
+    /// Returns the bytes backing the buffer if the buffer owns them.
+    pub fn into_vec(self) -> Option<Vec<u8>> {
+        match self.slice {
+            Storage::Owned(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Starts recording which objects are deserialized from which byte ranges of the buffer. This
+    /// is meant for debugging and reverse engineering packet layouts, see [`Trace`].
+    #[cfg(feature = "trace")]
+    pub fn enable_trace(&mut self) {
+        self.trace = Some(Trace::default());
+    }
+
+    /// Stops recording and returns the trace recorded so far.
+    #[cfg(feature = "trace")]
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+
+    /// Runs the closure and records the byte range it consumed under the specified name if tracing
+    /// is enabled. Objects traced from within the closure are recorded one level deeper.
+    #[cfg(feature = "trace")]
+    #[inline]
+    pub fn trace<T>(
+        &mut self,
+        name: &'static str,
+        f: impl FnOnce(&mut Self) -> Option<T>,
+    ) -> Option<T> {
+        let depth = match self.trace.as_mut() {
+            Some(trace) => trace.enter(),
+            None => return f(self),
+        };
+
+        let start = self.offset;
+        let val = f(self);
+
+        if let Some(trace) = self.trace.as_mut() {
+            trace.exit(name, start, self.offset, depth, val.is_some());
+        }
+
+        val
+    }
+
+    /// Runs the closure. Nothing is recorded unless the `trace` feature is enabled, in which case
+    /// this records the byte range the closure consumed once tracing is enabled on the buffer.
+    #[cfg(not(feature = "trace"))]
+    #[inline(always)]
+    pub fn trace<T>(
+        &mut self,
+        _name: &'static str,
+        f: impl FnOnce(&mut Self) -> Option<T>,
+    ) -> Option<T> {
+        f(self)
+    }
+
     /// Resets the Buffer with zero allocation and zero overhead. Resets the offset and resizes
     /// the length back to the original capacity of the buffer.
     pub fn reset(&mut self) {
@@ -135,35 +310,223 @@ impl Buffer {
     }
 }
 
-impl AsRef<[u8]> for Buffer {
+impl AsRef<[u8]> for Buffer<'_> {
     fn as_ref(&self) -> &[u8] {
-        &self.slice[..self.offset]
+        &self.slice.bytes()[..self.offset]
     }
 }
 
-impl Deref for Buffer {
+impl Deref for Buffer<'_> {
     type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
-        &self.slice[..self.size]
+        &self.slice.bytes()[..self.size]
     }
 }
 
-impl DerefMut for Buffer {
+impl DerefMut for Buffer<'_> {
+    /// Panics if the buffer is read only, see [`Buffer::as_mut_slice`] for a fallible
+    /// alternative.
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.slice[..self.size]
+        match self.as_mut_slice() {
+            Some(slice) => slice,
+            None => panic!("Unable to mutably borrow a read only buffer"),
+        }
     }
 }
 
-impl From<Vec<u8>> for Buffer {
+impl From<Vec<u8>> for Buffer<'_> {
     fn from(value: Vec<u8>) -> Self {
         let len = value.len();
 
         Self {
-            slice: value,
+            slice: Storage::Owned(value),
             offset: 0,
             size: len,
             cap: len,
+            #[cfg(feature = "trace")]
+            trace: None,
         }
     }
 }
+
+impl<'a> From<&'a [u8]> for Buffer<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        let len = value.len();
+
+        Self {
+            slice: Storage::Borrowed(value),
+            offset: 0,
+            size: len,
+            cap: len,
+            #[cfg(feature = "trace")]
+            trace: None,
+        }
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for Buffer<'a> {
+    fn from(value: &'a [u8; N]) -> Self {
+        Self::from(&value[..])
+    }
+}
+
+impl<'a> From<&'a mut [u8]> for Buffer<'a> {
+    fn from(value: &'a mut [u8]) -> Self {
+        let len = value.len();
+
+        Self {
+            slice: Storage::BorrowedMut(value),
+            offset: 0,
+            size: len,
+            cap: len,
+            #[cfg(feature = "trace")]
+            trace: None,
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for Buffer<'_> {
+    /// The buffer shares the bytes without copying them and is read only.
+    fn from(value: bytes::Bytes) -> Self {
+        let len = value.len();
+
+        Self {
+            slice: Storage::Shared(value),
+            offset: 0,
+            size: len,
+            cap: len,
+            #[cfg(feature = "trace")]
+            trace: None,
+        }
+    }
+}
+
+/// Take is a bounded view over a length-delimited region of a [`Buffer`] returned by
+/// [`Buffer::take`]. It dereferences to a buffer over the bytes of the region only and hands the
+/// trace, if any, back to the buffer it was taken from once dropped.
+pub struct Take<'a> {
+    view: Buffer<'a>,
+    #[cfg(feature = "trace")]
+    trace: &'a mut Option<Trace>,
+    #[cfg(feature = "trace")]
+    start: usize,
+}
+
+impl Take<'_> {
+    /// Returns true if every byte of the region has been consumed.
+    pub fn is_consumed(&self) -> bool {
+        self.view.remaining() == 0
+    }
+}
+
+impl<'a> Deref for Take<'a> {
+    type Target = Buffer<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl<'a> DerefMut for Take<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.view
+    }
+}
+
+#[cfg(feature = "trace")]
+impl Drop for Take<'_> {
+    fn drop(&mut self) {
+        *self.trace = self.view.trace.take();
+        if let Some(trace) = self.trace.as_mut() {
+            trace.origin -= self.start;
+        }
+    }
+}
+
+mod tests {
+    ///
+    /// Tests that a bounded view cannot read past its region and that the parent buffer is
+    /// advanced past the whole region once the view is dropped.
+    ///
+    #[test]
+    pub fn take() {
+        use crate::{Binary, Buffer, U8};
+
+        let mut buffer = Buffer::from(vec![1, 2, 3, 4, 5]);
+        {
+            let mut view = buffer.take(3).unwrap();
+
+            assert_eq!(U8::deserialize(&mut view).unwrap().get(), 1);
+            assert_eq!(view.remaining(), 2);
+            assert!(!view.is_consumed());
+
+            let mut rest = [0u8; 4];
+            assert_eq!(view.read(&mut rest), 2);
+            assert!(U8::deserialize(&mut view).is_none());
+
+            view.reset();
+            assert_eq!(view.remaining(), 3);
+            assert_eq!(view.write(&[9, 9, 9, 9]), 3);
+        }
+
+        assert_eq!(&buffer[..], &[9, 9, 9, 4, 5]);
+
+        assert_eq!(buffer.offset(), 3);
+        assert_eq!(buffer.remaining(), 2);
+        assert!(buffer.take(3).is_none());
+
+        drop(buffer.take(1).unwrap());
+        assert_eq!(U8::deserialize(&mut buffer).unwrap().get(), 5);
+    }
+
+    ///
+    /// Tests reading from a borrowed slice and writing into a borrowed mutable slice.
+    ///
+    #[test]
+    pub fn borrowed() {
+        use crate::{Binary, Buffer, LE, U16};
+
+        let mut bytes = [0u8; 2];
+        let mut buffer = Buffer::from(&mut bytes[..]);
+        U16::<LE>::new(0x0201).serialize(&mut buffer);
+        assert_eq!(bytes, [0x01, 0x02]);
+
+        let mut buffer = Buffer::from(&bytes);
+        assert!(buffer.is_read_only());
+        assert!(buffer.as_mut_slice().is_none());
+        assert_eq!(buffer.write(&[0xff]), 0);
+        assert_eq!(U16::<LE>::deserialize(&mut buffer).unwrap().get(), 0x0201);
+    }
+
+    ///
+    /// Tests writing through a mutable dereference of owned and mutably borrowed buffers.
+    ///
+    #[test]
+    pub fn deref_mut() {
+        use crate::Buffer;
+
+        let mut buffer = Buffer::from(vec![1, 2, 3]);
+        buffer[0] = 4;
+        buffer.as_mut()[1..].copy_from_slice(&[5, 6]);
+        assert_eq!(&buffer[..], &[4, 5, 6]);
+
+        let mut bytes = [0u8; 2];
+        let mut buffer = Buffer::from(&mut bytes[..]);
+        buffer[..].fill(7);
+        assert_eq!(bytes, [7, 7]);
+    }
+
+    ///
+    /// Tests that mutably dereferencing a read only buffer panics.
+    ///
+    #[test]
+    #[should_panic]
+    pub fn deref_mut_read_only() {
+        use crate::Buffer;
+
+        let mut buffer = Buffer::from(&[1u8, 2][..]);
+        buffer[0] = 3;
+    }
+}
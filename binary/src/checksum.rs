@@ -0,0 +1,389 @@
+use crate::Buffer;
+use core::fmt::{Display, Formatter};
+
+/// Checksum is implemented by the algorithms that can protect a region of a [`Buffer`], see
+/// [`Buffer::write_checked`] and [`Buffer::read_checked`]. The digest is the trailer written
+/// after the region, already in its byte order on the wire.
+pub trait Checksum {
+    type Digest: AsRef<[u8]>;
+
+    /// The number of bytes of the trailer.
+    const LEN: usize;
+
+    fn update(&mut self, bytes: &[u8]);
+    fn finish(self) -> Self::Digest;
+}
+
+/// ChecksumError is returned when a checksummed region cannot be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumError {
+    /// The buffer does not hold the region and its trailer.
+    Truncated,
+    /// The trailer does not match the checksum computed over the region.
+    Mismatch,
+    /// The checksum matched but the region could not be deserialized.
+    Invalid,
+}
+
+impl Display for ChecksumError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ChecksumError::Truncated => write!(f, "checksummed region is truncated"),
+            ChecksumError::Mismatch => write!(f, "checksum mismatch"),
+            ChecksumError::Invalid => write!(f, "checksummed region is invalid"),
+        }
+    }
+}
+
+impl core::error::Error for ChecksumError {}
+
+impl<'a> Buffer<'a> {
+    /// Writes the region produced by the closure followed by the trailer of the checksum
+    /// computed over the region.
+    pub fn write_checked<C: Checksum>(&mut self, mut checksum: C, f: impl FnOnce(&mut Self)) {
+        let start = self.offset();
+        f(self);
+
+        let len = self.offset() - start;
+        self.set_offset(start);
+        checksum.update(self.get(len, true));
+        self.write(checksum.finish().as_ref());
+    }
+
+    /// Verifies the checksum over the next len bytes against the trailer following them and then
+    /// reads the region using the closure, which cannot read past the region. The buffer is
+    /// advanced past the trailer once the checksum matches.
+    pub fn read_checked<C: Checksum, T>(
+        &mut self,
+        mut checksum: C,
+        len: usize,
+        f: impl FnOnce(&mut Buffer<'_>) -> Option<T>,
+    ) -> Result<T, ChecksumError> {
+        let end = len.checked_add(C::LEN).ok_or(ChecksumError::Truncated)?;
+        if self.remaining() < end {
+            return Err(ChecksumError::Truncated);
+        }
+
+        let start = self.offset();
+        checksum.update(self.get(len, true));
+        if checksum.finish().as_ref() != self.get(C::LEN, false) {
+            self.set_offset(start);
+            return Err(ChecksumError::Mismatch);
+        }
+
+        self.set_offset(start);
+        let mut region = self.take(len).ok_or(ChecksumError::Truncated)?;
+        let val = f(&mut region);
+        drop(region);
+        self.advance(C::LEN);
+
+        val.ok_or(ChecksumError::Invalid)
+    }
+}
+
+/// The lookup table of the reflected CRC-32 polynomial used by zlib, PNG and zip.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                crc >> 1 ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Crc32 computes the CRC-32 (IEEE) checksum, written as a little endian u32.
+#[derive(Debug, Clone)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Self { crc: !0 }
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Crc32 {
+    type Digest = [u8; 4];
+    const LEN: usize = 4;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.crc = CRC32_TABLE[((self.crc ^ b as u32) & 0xff) as usize] ^ self.crc >> 8;
+        }
+    }
+
+    fn finish(self) -> [u8; 4] {
+        (!self.crc).to_le_bytes()
+    }
+}
+
+/// Adler32 computes the Adler-32 checksum used by zlib, written as a big endian u32.
+#[derive(Debug, Clone)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    const MOD: u32 = 65521;
+    /// The largest number of bytes that can be summed before the sums have to be reduced.
+    const NMAX: usize = 5552;
+
+    pub fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Adler32 {
+    type Digest = [u8; 4];
+    const LEN: usize = 4;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(Self::NMAX) {
+            for &byte in chunk {
+                self.a += byte as u32;
+                self.b += self.a;
+            }
+
+            self.a %= Self::MOD;
+            self.b %= Self::MOD;
+        }
+    }
+
+    fn finish(self) -> [u8; 4] {
+        (self.b << 16 | self.a).to_be_bytes()
+    }
+}
+
+/// The round constants of SHA-256.
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Sha256 computes the SHA-256 digest of the region.
+#[derive(Debug, Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    block: [u8; 64],
+    filled: usize,
+    len: u64,
+}
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Self {
+            state: [
+                0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+                0x5be0cd19,
+            ],
+            block: [0; 64],
+            filled: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the digest of the bytes.
+    pub fn digest(bytes: &[u8]) -> [u8; 32] {
+        let mut sha = Self::new();
+        sha.update(bytes);
+        sha.finish()
+    }
+
+    fn compress(&mut self) {
+        let mut w = [0u32; 64];
+        for (i, word) in self.block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes(word.try_into().unwrap());
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ w[i - 15] >> 3;
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ w[i - 2] >> 10;
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(SHA256_K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (state, val) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *state = state.wrapping_add(val);
+        }
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Sha256 {
+    type Digest = [u8; 32];
+    const LEN: usize = 32;
+
+    fn update(&mut self, mut bytes: &[u8]) {
+        self.len += bytes.len() as u64;
+
+        while !bytes.is_empty() {
+            let n = (64 - self.filled).min(bytes.len());
+            self.block[self.filled..self.filled + n].copy_from_slice(&bytes[..n]);
+            self.filled += n;
+            bytes = &bytes[n..];
+
+            if self.filled == 64 {
+                self.compress();
+                self.filled = 0;
+            }
+        }
+    }
+
+    fn finish(mut self) -> [u8; 32] {
+        let bits = self.len * 8;
+
+        self.update(&[0x80]);
+        while self.filled != 56 {
+            self.update(&[0]);
+        }
+        self.update(&bits.to_be_bytes());
+
+        let mut digest = [0u8; 32];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+/// PacketChecksum computes the checksum of encrypted Bedrock game packets: the first 8 bytes of
+/// the SHA-256 digest of the little endian send counter, the payload and the secret key.
+#[derive(Debug, Clone)]
+pub struct PacketChecksum<'k> {
+    sha: Sha256,
+    key: &'k [u8],
+}
+
+impl<'k> PacketChecksum<'k> {
+    pub fn new(counter: u64, key: &'k [u8]) -> Self {
+        let mut sha = Sha256::new();
+        sha.update(&counter.to_le_bytes());
+        Self { sha, key }
+    }
+}
+
+impl Checksum for PacketChecksum<'_> {
+    type Digest = [u8; 8];
+    const LEN: usize = 8;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.sha.update(bytes);
+    }
+
+    fn finish(mut self) -> [u8; 8] {
+        self.sha.update(self.key);
+        let digest = self.sha.finish();
+        digest[..8].try_into().unwrap()
+    }
+}
+
+mod tests {
+    ///
+    /// Tests the algorithms against known digests and the checked region helpers.
+    ///
+    #[test]
+    pub fn checksum() {
+        use crate::{Adler32, Binary, Buffer, Checksum, ChecksumError, Crc32, Sha256, BE, U16};
+
+        fn digest<C: Checksum>(mut checksum: C, bytes: &[u8]) -> C::Digest {
+            checksum.update(bytes);
+            checksum.finish()
+        }
+
+        assert_eq!(
+            digest(Crc32::new(), b"123456789"),
+            0xcbf43926u32.to_le_bytes()
+        );
+        assert_eq!(
+            digest(Adler32::new(), b"Wikipedia"),
+            0x11e60398u32.to_be_bytes()
+        );
+        assert_eq!(
+            Sha256::digest(b"abc")[..8],
+            [0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea]
+        );
+        assert_eq!(
+            Sha256::digest(&[b'a'; 1000])[24..],
+            [0x5a, 0xdb, 0x5d, 0xb1, 0xb9, 0x73, 0x7e, 0xa3]
+        );
+
+        let mut buf = Buffer::new(6);
+        buf.write_checked(Crc32::new(), |buf| U16::<BE>::new(0x1234).serialize(buf));
+        assert_eq!(buf.offset(), 6);
+
+        buf.set_offset(0);
+        let val = buf.read_checked(Crc32::new(), 2, U16::<BE>::deserialize);
+        assert_eq!(val.map(U16::get), Ok(0x1234));
+        assert_eq!(buf.remaining(), 0);
+
+        buf.as_mut_slice().unwrap()[5] ^= 1;
+        buf.set_offset(0);
+        let val = buf.read_checked(Crc32::new(), 2, U16::<BE>::deserialize);
+        assert_eq!(val.unwrap_err(), ChecksumError::Mismatch);
+        assert_eq!(buf.offset(), 0);
+
+        let val = buf.read_checked(Crc32::new(), usize::MAX, U16::<BE>::deserialize);
+        assert_eq!(val.unwrap_err(), ChecksumError::Truncated);
+        assert_eq!(buf.offset(), 0);
+    }
+}
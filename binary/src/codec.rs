@@ -0,0 +1,200 @@
+use crate::{Binary, Buffer, Prefix};
+use bytes::{Buf, BytesMut};
+use std::io::{Error, ErrorKind};
+use std::marker::PhantomData;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Codec frames a stream of bytes into [`Binary`] objects where each frame is prefixed by its
+/// length encoded using the prefix P. Frames larger than the maximum frame size are rejected
+/// both while encoding and decoding, and partially received frames are buffered until the rest
+/// of the frame arrives.
+pub struct Codec<P: Prefix, B: Binary> {
+    max: usize,
+    scratch: Option<Buffer<'static>>,
+    _marker: PhantomData<(P, B)>,
+}
+
+impl<P: Prefix, B: Binary> Codec<P, B> {
+    /// Creates and returns a new Codec which accepts frames of at most max bytes. The buffer
+    /// objects are encoded into is allocated once the first object is encoded.
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            scratch: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the maximum size of a frame in bytes excluding its prefix.
+    pub fn max_frame(&self) -> usize {
+        self.max
+    }
+}
+
+impl<P: Prefix, B: Binary> Decoder for Codec<P, B> {
+    type Item = B;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<B>, Error> {
+        let mut buf = Buffer::from(&src[..]);
+
+        let len = match P::decode(&mut buf) {
+            Some(len) => len,
+            None if buf.remaining() == 0 && incomplete::<P>(src) => return Ok(None),
+            None => return Err(Error::new(ErrorKind::InvalidData, "invalid frame prefix")),
+        };
+
+        if len > self.max {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "frame of {} bytes exceeds the maximum of {} bytes",
+                    len, self.max
+                ),
+            ));
+        }
+
+        let header = buf.offset();
+        if buf.remaining() < len {
+            src.reserve(header + len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(header);
+        let frame = src.split_to(len);
+
+        let mut buf = Buffer::from(&frame[..]);
+        match B::deserialize(&mut buf) {
+            Some(_) if buf.remaining() != 0 => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{} trailing bytes after the frame", buf.remaining()),
+            )),
+            Some(val) => Ok(Some(val)),
+            None => Err(Error::new(ErrorKind::InvalidData, "unable to decode frame")),
+        }
+    }
+}
+
+/// Returns true if the bytes are the start of a valid prefix, that is if the prefix decodes once
+/// the bytes are followed by zeros. A prefix that fails to decode even then, such as a varint
+/// whose last byte still has its continuation bit set, is invalid rather than incomplete.
+fn incomplete<P: Prefix>(src: &[u8]) -> bool {
+    let mut bytes = [0u8; 16];
+    let n = src.len().min(bytes.len());
+    bytes[..n].copy_from_slice(&src[..n]);
+
+    P::decode(&mut Buffer::from(&bytes[..])).is_some()
+}
+
+impl<P: Prefix, B: Binary> Encoder<B> for Codec<P, B> {
+    type Error = Error;
+
+    fn encode(&mut self, item: B, dst: &mut BytesMut) -> Result<(), Error> {
+        // One extra byte is allocated so that objects which do not fit in a frame can be told
+        // apart from those that fill it exactly.
+        let max = self.max;
+        let scratch = self
+            .scratch
+            .get_or_insert_with(|| Buffer::new(max.saturating_add(1)));
+
+        scratch.reset();
+        item.serialize(scratch);
+
+        let len = scratch.offset();
+        if len > self.max {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("frame exceeds the maximum of {} bytes", self.max),
+            ));
+        }
+
+        let mut bytes = [0u8; 16];
+        let mut header = Buffer::from(&mut bytes[..]);
+        P::encode(len, &mut header);
+
+        dst.reserve(header.offset() + len);
+        dst.extend_from_slice(header.as_ref());
+        dst.extend_from_slice(scratch.as_ref());
+
+        Ok(())
+    }
+}
+
+mod tests {
+    ///
+    /// Tests framing objects over an in-memory duplex stream.
+    ///
+    #[test]
+    pub fn duplex() {
+        use crate::{CString, Codec, W32};
+        use futures::{SinkExt, StreamExt};
+        use tokio_util::codec::{FramedRead, FramedWrite};
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let (client, server) = tokio::io::duplex(8);
+            let mut writer = FramedWrite::new(client, Codec::<W32, CString<W32>>::new(64));
+            let mut reader = FramedRead::new(server, Codec::<W32, CString<W32>>::new(64));
+
+            let task = tokio::spawn(async move {
+                for msg in ["Hello", "World", "A somewhat longer message than the pipe"] {
+                    writer.send(CString::new(msg.to_string())).await.unwrap();
+                }
+            });
+
+            assert_eq!(reader.next().await.unwrap().unwrap().get(), "Hello");
+            assert_eq!(reader.next().await.unwrap().unwrap().get(), "World");
+            assert_eq!(
+                reader.next().await.unwrap().unwrap().get(),
+                "A somewhat longer message than the pipe"
+            );
+
+            task.await.unwrap();
+            assert!(reader.next().await.is_none());
+        });
+    }
+
+    ///
+    /// Tests that partial frames and prefixes are buffered and that oversized frames, frames with
+    /// trailing bytes and invalid prefixes are rejected.
+    ///
+    #[test]
+    pub fn frames() {
+        use crate::{Codec, BE, U32, U8, W32};
+        use bytes::BytesMut;
+        use tokio_util::codec::{Decoder, Encoder};
+
+        let mut codec = Codec::<U32<BE>, U8>::new(1);
+
+        let mut src = BytesMut::from(&[0x00, 0x00][..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+
+        src.extend_from_slice(&[0x00, 0x01]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+
+        src.extend_from_slice(&[0x2a]);
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap().get(), 0x2a);
+        assert!(src.is_empty());
+
+        let mut dst = BytesMut::new();
+        codec.encode(U8::new(0x2a), &mut dst).unwrap();
+        assert_eq!(&dst[..], &[0x00, 0x00, 0x00, 0x01, 0x2a]);
+
+        let mut src = BytesMut::from(&[0x00, 0x00, 0x00, 0x02, 0x00, 0x00][..]);
+        assert!(codec.decode(&mut src).is_err());
+
+        let mut codec = Codec::<U32<BE>, U8>::new(2);
+        let mut src = BytesMut::from(&[0x00, 0x00, 0x00, 0x02, 0x2a, 0x2b][..]);
+        assert!(codec.decode(&mut src).is_err());
+
+        let mut codec = Codec::<W32, U8>::new(usize::MAX);
+        let mut src = BytesMut::from(&[0x81, 0x80][..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+
+        src.extend_from_slice(&[0x80, 0x80, 0x80]);
+        assert!(codec.decode(&mut src).is_err());
+    }
+}
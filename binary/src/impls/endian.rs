@@ -0,0 +1,112 @@
+//! Wrappers mirroring the numeric wrappers whose byte order is only known at runtime. They are
+//! serialized through [`EndianBinary`] with an [`Endian`] supplied by the caller rather than
+//! through [`crate::Binary`], so they live in their own module instead of clashing with the
+//! statically ordered wrappers of the same name.
+
+use crate::{generate, Buffer, Endian, EndianBinary};
+
+generate!(U16, <>, u16);
+generate!(I16, <>, i16);
+generate!(U24, <>, u32);
+generate!(I24, <>, i32);
+generate!(U32, <>, u32);
+generate!(I32, <>, i32);
+generate!(U48, <>, u64);
+generate!(U64, <>, u64);
+generate!(I64, <>, i64);
+generate!(U128, <>, u128);
+generate!(I128, <>, i128);
+generate!(F16, <>, f32);
+generate!(F32, <>, f32);
+generate!(F64, <>, f64);
+
+macro_rules! impl_endian {
+    ($wrapper:ident, $read_method:ident, $write_method:ident) => {
+        impl EndianBinary for $wrapper {
+            fn serialize_endian(&self, endian: Endian, buf: &mut Buffer) {
+                endian.$write_method(*self.as_ref(), buf)
+            }
+
+            fn deserialize_endian(endian: Endian, buf: &mut Buffer) -> Option<Self> {
+                buf.trace(stringify!($wrapper), |buf| {
+                    endian.$read_method(buf).map(Self::new)
+                })
+            }
+        }
+    };
+}
+
+impl_endian!(U16, read_u16, write_u16);
+impl_endian!(I16, read_i16, write_i16);
+impl_endian!(U24, read_u24, write_u24);
+impl_endian!(I24, read_i24, write_i24);
+impl_endian!(U32, read_u32, write_u32);
+impl_endian!(I32, read_i32, write_i32);
+impl_endian!(U48, read_u48, write_u48);
+impl_endian!(U64, read_u64, write_u64);
+impl_endian!(I64, read_i64, write_i64);
+impl_endian!(U128, read_u128, write_u128);
+impl_endian!(I128, read_i128, write_i128);
+impl_endian!(F16, read_f16, write_f16);
+impl_endian!(F32, read_f32, write_f32);
+impl_endian!(F64, read_f64, write_f64);
+
+mod tests {
+    ///
+    /// Tests reading the same bytes in both byte orders and that writing them back in the byte
+    /// order they were read in reproduces the bytes.
+    ///
+    #[test]
+    pub fn endian() {
+        use crate::endian::{F16, I24, U16, U48};
+        use crate::{Buffer, Endian, EndianBinary};
+
+        let bytes = [
+            0x01, 0x02, 0x80, 0x00, 0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x3c, 0x00, 0x00,
+            0x00, 0x00, 0x2a,
+        ];
+
+        let read = |endian| {
+            let mut buf = Buffer::from(&bytes);
+            let vals = (
+                U16::deserialize_endian(endian, &mut buf).unwrap(),
+                I24::deserialize_endian(endian, &mut buf).unwrap(),
+                U48::deserialize_endian(endian, &mut buf).unwrap(),
+                F16::deserialize_endian(endian, &mut buf).unwrap(),
+                u32::deserialize_endian(endian, &mut buf).unwrap(),
+            );
+            assert_eq!(buf.remaining(), 0);
+
+            let mut buf = Buffer::new(bytes.len());
+            vals.0.serialize_endian(endian, &mut buf);
+            vals.1.serialize_endian(endian, &mut buf);
+            vals.2.serialize_endian(endian, &mut buf);
+            vals.3.serialize_endian(endian, &mut buf);
+            vals.4.serialize_endian(endian, &mut buf);
+            assert_eq!(&buf[..], &bytes);
+
+            (
+                vals.0.get(),
+                vals.1.get(),
+                vals.2.get(),
+                vals.3.get(),
+                vals.4,
+            )
+        };
+
+        assert_eq!(
+            read(Endian::Little),
+            (
+                0x0201,
+                0x01_0080,
+                0x0605_0403_0201,
+                15.0 * 2f32.powi(-22),
+                0x2a00_0000
+            )
+        );
+        assert_eq!(
+            read(Endian::Big),
+            (0x0102, -0x7f_ffff, 0x0102_0304_0506, 1.0, 0x2a)
+        );
+    }
+}
@@ -0,0 +1,115 @@
+/// Generates an enum whose discriminant is serialized using the given wrapper, such as `U8`,
+/// `W32`, `V32` or `I32<LE>`. Besides the listed variants the enum has an `Unknown` variant
+/// holding any other discriminant, so values added by newer versions of the protocol are kept
+/// and serialized back exactly as they were read.
+///
+/// ```
+/// use binary::{binary_enum, V32};
+///
+/// binary_enum! {
+///     pub enum GameMode: V32 {
+///         Survival = 0,
+///         Creative = 1,
+///         Adventure = 2,
+///     }
+/// }
+///
+/// assert_eq!(GameMode::from_raw(1), GameMode::Creative);
+/// assert_eq!(GameMode::from_raw(6).to_raw(), 6);
+///
+/// let mut buf = binary::Buffer::new(1);
+/// binary::Binary::serialize(&GameMode::Adventure, &mut buf);
+/// assert_eq!(&buf[..], &[4]);
+/// ```
+#[macro_export]
+macro_rules! binary_enum {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident: $repr:ty {
+            $( $(#[$variant_attr:meta])* $variant:ident = $val:literal ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $( $(#[$variant_attr])* $variant, )*
+            /// A discriminant that none of the variants use.
+            Unknown(<$repr as core::ops::Deref>::Target),
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            /// Returns the variant of the discriminant, or `Unknown` if no variant uses it.
+            pub fn from_raw(raw: <$repr as core::ops::Deref>::Target) -> Self {
+                match raw {
+                    $( $val => Self::$variant, )*
+                    raw => Self::Unknown(raw),
+                }
+            }
+
+            /// Returns the discriminant of the variant.
+            pub fn to_raw(self) -> <$repr as core::ops::Deref>::Target {
+                match self {
+                    $( Self::$variant => $val, )*
+                    Self::Unknown(raw) => raw,
+                }
+            }
+
+            /// Returns false if the discriminant is not one of the listed variants.
+            pub fn is_known(self) -> bool {
+                !matches!(self, Self::Unknown(_))
+            }
+        }
+
+        impl $crate::Binary for $name {
+            fn serialize(&self, buf: &mut $crate::Buffer) {
+                $crate::Binary::serialize(&<$repr>::new(self.to_raw()), buf);
+            }
+
+            fn deserialize(buf: &mut $crate::Buffer) -> Option<Self> {
+                buf.trace(stringify!($name), |buf| {
+                    let raw = <$repr as $crate::Binary>::deserialize(buf)?.get();
+                    Some(Self::from_raw(raw))
+                })
+            }
+
+            fn skip(buf: &mut $crate::Buffer) -> Option<()> {
+                <$repr as $crate::Binary>::skip(buf)
+            }
+        }
+
+        $crate::impl_binary_ctx!($name);
+
+        $crate::__impl_arbitrary!($name, |u| Ok(Self::from_raw(u.arbitrary()?)));
+    };
+}
+
+mod tests {
+    ///
+    /// Tests that known discriminants map to variants and unknown ones are kept as they are.
+    ///
+    #[test]
+    pub fn enums() {
+        use crate::{Binary, Buffer, I32, LE};
+
+        binary_enum! {
+            pub enum TextType: I32<LE> {
+                Raw = 0,
+                Chat = 1,
+                Tip = -1,
+            }
+        }
+
+        let mut buf = Buffer::from(&[1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 9, 0, 0, 0][..]);
+        assert_eq!(TextType::deserialize(&mut buf), Some(TextType::Chat));
+        assert_eq!(TextType::deserialize(&mut buf), Some(TextType::Tip));
+
+        let unknown = TextType::deserialize(&mut buf).unwrap();
+        assert_eq!(unknown, TextType::Unknown(9));
+        assert!(!unknown.is_known());
+
+        let mut buf = Buffer::new(4);
+        unknown.serialize(&mut buf);
+        assert_eq!(&buf[..], &[9, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{Binary, ByteOrder, U16, U32, U64, U8, V32, V64, W32, W64};
+
+/// FlagRepr is implemented for the types a flag set generated by [`flags!`](crate::flags) can be
+/// serialized as. Bits that do not fit into the type are dropped when serializing.
+pub trait FlagRepr: Binary {
+    /// The number of bits the type is able to hold.
+    const BITS: u32;
+
+    fn from_bits(bits: u128) -> Self;
+    fn to_bits(&self) -> u128;
+}
+
+macro_rules! impl_flag_repr {
+    ($wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>, $ty:ty, $unsigned:ty) => {
+        impl<$($gen: $gen_constraint),*> FlagRepr for $wrapper<$($gen),*> {
+            const BITS: u32 = <$unsigned>::BITS;
+
+            fn from_bits(bits: u128) -> Self {
+                Self::new(bits as $unsigned as $ty)
+            }
+
+            fn to_bits(&self) -> u128 {
+                *self.as_ref() as $unsigned as u128
+            }
+        }
+    };
+}
+
+impl_flag_repr!(U8, <>, u8, u8);
+impl_flag_repr!(U16, <E: ByteOrder>, u16, u16);
+impl_flag_repr!(U32, <E: ByteOrder>, u32, u32);
+impl_flag_repr!(U64, <E: ByteOrder>, u64, u64);
+impl_flag_repr!(W32, <>, u32, u32);
+impl_flag_repr!(V32, <>, i32, u32);
+impl_flag_repr!(W64, <>, u64, u64);
+impl_flag_repr!(V64, <>, i64, u64);
+
+/// Two 64-bit words are serialized with the lower bits first.
+impl<B: FlagRepr> FlagRepr for [B; 2] {
+    const BITS: u32 = B::BITS * 2;
+
+    fn from_bits(bits: u128) -> Self {
+        [B::from_bits(bits), B::from_bits(bits >> B::BITS)]
+    }
+
+    fn to_bits(&self) -> u128 {
+        self[0].to_bits() | self[1].to_bits() << B::BITS
+    }
+}
+
+/// Generates a set of flags defined by the position of every named bit, serialized using any
+/// [`FlagRepr`] such as `W64`, `U32<LE>` or `[V64; 2]`. The bits are stored in a u128, and bits
+/// without a name are kept so that they are serialized back exactly as they were read.
+///
+/// ```
+/// use binary::{flags, W64};
+///
+/// flags! {
+///     pub struct Abilities: W64 {
+///         BUILD = 0,
+///         MINE = 1,
+///         FLYING = 9,
+///     }
+/// }
+///
+/// let mut abilities = Abilities::BUILD | Abilities::MINE;
+/// abilities.set(Abilities::MINE, false);
+/// assert!(abilities.contains(Abilities::BUILD));
+/// let abilities = abilities | Abilities::from_bits(1 << 40);
+/// assert_eq!(format!("{:?}", abilities), "Abilities(BUILD | 0x10000000000)");
+/// ```
+#[macro_export]
+macro_rules! flags {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $repr:ty {
+            $( $(#[$flag_attr:meta])* $flag:ident = $bit:literal ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        $vis struct $name {
+            bits: u128,
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            $( $(#[$flag_attr])* pub const $flag: Self = Self { bits: 1 << $bit }; )*
+
+            /// Every flag that has a name.
+            const NAMED: &'static [(&'static str, Self)] =
+                &[$( (stringify!($flag), Self::$flag) ),*];
+
+            /// Returns a set without any flags.
+            pub const fn empty() -> Self {
+                Self { bits: 0 }
+            }
+
+            /// Returns a set of all the named flags.
+            pub const fn all() -> Self {
+                Self { bits: 0 $( | 1 << $bit )* }
+            }
+
+            /// Returns a set of the bits, including bits without a name.
+            pub const fn from_bits(bits: u128) -> Self {
+                Self { bits }
+            }
+
+            /// Returns the bits of the set.
+            pub const fn bits(self) -> u128 {
+                self.bits
+            }
+
+            /// Returns the bits of the set that do not belong to a named flag.
+            pub const fn unknown_bits(self) -> u128 {
+                self.bits & !Self::all().bits
+            }
+
+            /// Returns true if no bit is set.
+            pub const fn is_empty(self) -> bool {
+                self.bits == 0
+            }
+
+            /// Returns true if every flag of other is set.
+            pub const fn contains(self, other: Self) -> bool {
+                self.bits & other.bits == other.bits
+            }
+
+            /// Sets every flag of other.
+            pub fn insert(&mut self, other: Self) {
+                self.bits |= other.bits;
+            }
+
+            /// Clears every flag of other.
+            pub fn remove(&mut self, other: Self) {
+                self.bits &= !other.bits;
+            }
+
+            /// Sets or clears every flag of other depending on the value.
+            pub fn set(&mut self, other: Self, value: bool) {
+                if value {
+                    self.insert(other)
+                } else {
+                    self.remove(other)
+                }
+            }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                Self { bits: self.bits | rhs.bits }
+            }
+        }
+
+        impl core::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.bits |= rhs.bits;
+            }
+        }
+
+        impl core::ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                Self { bits: self.bits & rhs.bits }
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}(", stringify!($name))?;
+
+                let mut first = true;
+                for (name, flag) in Self::NAMED {
+                    if self.contains(*flag) {
+                        if !first {
+                            write!(f, " | ")?;
+                        }
+                        write!(f, "{}", name)?;
+                        first = false;
+                    }
+                }
+
+                let unknown = self.unknown_bits();
+                if unknown != 0 || first {
+                    if !first {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{:#x}", unknown)?;
+                }
+
+                write!(f, ")")
+            }
+        }
+
+        impl $crate::Binary for $name {
+            fn serialize(&self, buf: &mut $crate::Buffer) {
+                let repr = <$repr as $crate::FlagRepr>::from_bits(self.bits);
+                $crate::Binary::serialize(&repr, buf);
+            }
+
+            fn deserialize(buf: &mut $crate::Buffer) -> Option<Self> {
+                buf.trace(stringify!($name), |buf| {
+                    let repr = <$repr as $crate::Binary>::deserialize(buf)?;
+                    Some(Self::from_bits($crate::FlagRepr::to_bits(&repr)))
+                })
+            }
+
+            fn skip(buf: &mut $crate::Buffer) -> Option<()> {
+                <$repr as $crate::Binary>::skip(buf)
+            }
+        }
+
+        $crate::impl_binary_ctx!($name);
+
+        // Only the bits the representation is able to hold are generated.
+        $crate::__impl_arbitrary!($name, |u| {
+            let repr = <$repr as $crate::FlagRepr>::from_bits(u.arbitrary()?);
+            Ok(Self::from_bits($crate::FlagRepr::to_bits(&repr)))
+        });
+    };
+}
+
+mod tests {
+    ///
+    /// Tests the encodings of flag sets and that unknown bits survive a round trip.
+    ///
+    #[test]
+    pub fn flags() {
+        use crate::{Binary, Buffer, LE, U32, V64};
+
+        flags! {
+            pub struct InputFlags: [V64; 2] {
+                ASCEND = 0,
+                JUMPING = 3,
+                START_GLIDING = 64,
+            }
+        }
+
+        flags! {
+            pub struct HeaderFlags: U32<LE> {
+                COMPRESSED = 1,
+            }
+        }
+
+        let flags = InputFlags::JUMPING | InputFlags::START_GLIDING;
+        let mut buf = Buffer::new(2);
+        flags.serialize(&mut buf);
+        assert_eq!(&buf[..], &[0x10, 0x02]);
+
+        buf.set_offset(0);
+        assert_eq!(InputFlags::deserialize(&mut buf), Some(flags));
+        assert_eq!(
+            format!("{:?}", flags),
+            "InputFlags(JUMPING | START_GLIDING)"
+        );
+        assert_eq!(format!("{:?}", InputFlags::empty()), "InputFlags(0x0)");
+
+        let mut buf = Buffer::from(&[0x03, 0, 0, 0x80][..]);
+        let header = HeaderFlags::deserialize(&mut buf).unwrap();
+        assert!(header.contains(HeaderFlags::COMPRESSED));
+        assert_eq!(header.unknown_bits(), 0x8000_0001);
+
+        let mut buf = Buffer::new(4);
+        header.serialize(&mut buf);
+        assert_eq!(&buf[..], &[0x03, 0, 0, 0x80]);
+    }
+}
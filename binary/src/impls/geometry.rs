@@ -0,0 +1,350 @@
+use crate::{impl_binary_ctx, Binary, Buffer, F32, LE, V32, W32};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// BlockPos represents the position of a block in a world. It is serialized as a signed varint X,
+/// an unsigned varint Y and a signed varint Z. Negative Y values are written as their two's
+/// complement, as the game does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BlockPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Vec3 represents a position or a direction in a world, serialized as three little endian
+/// floats.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// Vec2 represents a pair of little endian floats, such as the pitch and yaw of a rotation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Rounds towards negative infinity. `f32::floor` is not used as it is only available with std.
+/// Values out of the range of i32 saturate.
+fn floor(val: f32) -> i32 {
+    let truncated = val as i32;
+    if (truncated as f32) > val {
+        truncated.saturating_sub(1)
+    } else {
+        truncated
+    }
+}
+
+impl BlockPos {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the position of the block containing the point.
+    pub fn containing(pos: Vec3) -> Self {
+        Self::new(floor(pos.x), floor(pos.y), floor(pos.z))
+    }
+
+    /// Returns the position offset by the given amount along every axis. Like the arithmetic
+    /// operators of BlockPos, it wraps around on overflow rather than panicking.
+    pub const fn offset(self, x: i32, y: i32, z: i32) -> Self {
+        Self::new(
+            self.x.wrapping_add(x),
+            self.y.wrapping_add(y),
+            self.z.wrapping_add(z),
+        )
+    }
+
+    /// Returns the centre of the block.
+    pub fn center(self) -> Vec3 {
+        Vec3::from(self) + Vec3::new(0.5, 0.5, 0.5)
+    }
+}
+
+impl Vec3 {
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the dot product of the two vectors.
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the squared length of the vector.
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    /// Returns the squared distance between the two points.
+    pub fn distance_squared(self, other: Self) -> f32 {
+        (self - other).length_squared()
+    }
+}
+
+impl Vec2 {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the dot product of the two vectors.
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the squared length of the vector.
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+}
+
+impl Binary for BlockPos {
+    fn serialize(&self, buf: &mut Buffer) {
+        V32::new(self.x).serialize(buf);
+        W32::new(self.y as u32).serialize(buf);
+        V32::new(self.z).serialize(buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("BlockPos", |buf| {
+            let x = V32::deserialize(buf)?.get();
+            let y = W32::deserialize(buf)?.get() as i32;
+            let z = V32::deserialize(buf)?.get();
+
+            Some(Self::new(x, y, z))
+        })
+    }
+}
+
+impl_binary_ctx!(BlockPos);
+
+impl Binary for Vec3 {
+    fn serialize(&self, buf: &mut Buffer) {
+        F32::<LE>::serialize_slice(&[self.x.into(), self.y.into(), self.z.into()], buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Vec3", |buf| {
+            let [x, y, z] = <[F32<LE>; 3]>::deserialize(buf)?;
+            Some(Self::new(x.get(), y.get(), z.get()))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        F32::<LE>::skip_many(buf, 3)
+    }
+}
+
+impl_binary_ctx!(Vec3);
+
+impl Binary for Vec2 {
+    fn serialize(&self, buf: &mut Buffer) {
+        F32::<LE>::serialize_slice(&[self.x.into(), self.y.into()], buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Vec2", |buf| {
+            let [x, y] = <[F32<LE>; 2]>::deserialize(buf)?;
+            Some(Self::new(x.get(), y.get()))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        F32::<LE>::skip_many(buf, 2)
+    }
+}
+
+impl_binary_ctx!(Vec2);
+
+/// Implements the arithmetic operators field by field using the given methods, so that BlockPos
+/// can wrap around on overflow, as positions received from clients are not trusted.
+macro_rules! impl_ops {
+    (
+        $name:ident, $ty:ty, [$add:ident, $sub:ident, $mul:ident, $div:ident, $neg:ident],
+        $($field:ident),*
+    ) => {
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field.$add(rhs.$field)),* }
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field.$sub(rhs.$field)),* }
+            }
+        }
+
+        impl Mul<$ty> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: $ty) -> Self {
+                Self { $($field: self.$field.$mul(rhs)),* }
+            }
+        }
+
+        impl Div<$ty> for $name {
+            type Output = Self;
+
+            fn div(self, rhs: $ty) -> Self {
+                Self { $($field: self.$field.$div(rhs)),* }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: self.$field.$neg()),* }
+            }
+        }
+    };
+}
+
+impl_ops!(
+    BlockPos,
+    i32,
+    [
+        wrapping_add,
+        wrapping_sub,
+        wrapping_mul,
+        wrapping_div,
+        wrapping_neg
+    ],
+    x,
+    y,
+    z
+);
+impl_ops!(Vec3, f32, [add, sub, mul, div, neg], x, y, z);
+impl_ops!(Vec2, f32, [add, sub, mul, div, neg], x, y);
+
+impl From<[i32; 3]> for BlockPos {
+    fn from([x, y, z]: [i32; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<BlockPos> for [i32; 3] {
+    fn from(pos: BlockPos) -> Self {
+        [pos.x, pos.y, pos.z]
+    }
+}
+
+impl From<[f32; 3]> for Vec3 {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<Vec3> for [f32; 3] {
+    fn from(vec: Vec3) -> Self {
+        [vec.x, vec.y, vec.z]
+    }
+}
+
+impl From<[f32; 2]> for Vec2 {
+    fn from([x, y]: [f32; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Vec2> for [f32; 2] {
+    fn from(vec: Vec2) -> Self {
+        [vec.x, vec.y]
+    }
+}
+
+/// Converts the position to the point at the lowest corner of the block.
+impl From<BlockPos> for Vec3 {
+    fn from(pos: BlockPos) -> Self {
+        Self::new(pos.x as f32, pos.y as f32, pos.z as f32)
+    }
+}
+
+/// Converts the point to the position of the block containing it, see [`BlockPos::containing`].
+impl From<Vec3> for BlockPos {
+    fn from(vec: Vec3) -> Self {
+        Self::containing(vec)
+    }
+}
+
+mod tests {
+    ///
+    /// Tests the encoding of the geometry types and the conversion between points and blocks.
+    ///
+    #[test]
+    pub fn geometry() {
+        use crate::{Binary, BlockPos, Buffer, Vec2, Vec3};
+
+        let pos = BlockPos::new(-1, -64, 300);
+        let mut buf = Buffer::new(9);
+        pos.serialize(&mut buf);
+        assert_eq!(
+            &buf[..],
+            &[0x01, 0xc0, 0xff, 0xff, 0xff, 0x0f, 0xd8, 0x04, 0x00]
+        );
+
+        buf.set_offset(0);
+        assert_eq!(BlockPos::deserialize(&mut buf), Some(pos));
+
+        let vec = Vec3::new(1.0, -0.5, 2.25);
+        let mut buf = Buffer::new(12);
+        vec.serialize(&mut buf);
+        assert_eq!(&buf[..4], &1.0f32.to_le_bytes());
+
+        buf.set_offset(0);
+        assert_eq!(Vec3::deserialize(&mut buf), Some(vec));
+
+        let mut buf = Buffer::from(&[0u8; 12][..]);
+        assert_eq!(Vec2::deserialize(&mut buf), Some(Vec2::default()));
+        assert_eq!(Vec2::deserialize(&mut buf), None);
+
+        assert_eq!(
+            BlockPos::from(Vec3::new(-0.5, 64.0, 3.9)),
+            BlockPos::new(-1, 64, 3)
+        );
+        assert_eq!(
+            BlockPos::new(1, 2, 3) - BlockPos::new(1, 1, 1),
+            BlockPos::new(0, 1, 2)
+        );
+        assert_eq!(BlockPos::new(0, 0, 0).center(), Vec3::new(0.5, 0.5, 0.5));
+    }
+
+    ///
+    /// Tests that points out of the range of i32 saturate and that NaN maps to zero rather than
+    /// overflowing.
+    ///
+    #[test]
+    pub fn containing() {
+        use crate::{BlockPos, Vec3};
+
+        let pos = BlockPos::containing(Vec3::new(f32::NEG_INFINITY, -3e9, f32::NAN));
+        assert_eq!(pos, BlockPos::new(i32::MIN, i32::MIN, 0));
+
+        let pos = BlockPos::from(Vec3::new(f32::INFINITY, 3e9, -f32::NAN));
+        assert_eq!(pos, BlockPos::new(i32::MAX, i32::MAX, 0));
+    }
+
+    ///
+    /// Tests that the arithmetic of BlockPos wraps around on overflow rather than panicking.
+    ///
+    #[test]
+    pub fn wrapping() {
+        use crate::BlockPos;
+
+        let min = BlockPos::new(i32::MIN, 0, i32::MAX);
+        assert_eq!(-min, BlockPos::new(i32::MIN, 0, -i32::MAX));
+        assert_eq!(min.offset(-1, 0, 1), BlockPos::new(i32::MAX, 0, i32::MIN));
+        assert_eq!(min + min, BlockPos::new(0, 0, -2));
+        assert_eq!(min - BlockPos::new(1, 0, -1), min.offset(-1, 0, 1));
+        assert_eq!(min * 2, min + min);
+        assert_eq!(min / -1, -min);
+    }
+}
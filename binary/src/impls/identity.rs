@@ -0,0 +1,168 @@
+use crate::{generate, impl_binary_ctx, Binary, Buffer, LE, U64, V64, W64};
+use core::fmt::{Display, Formatter};
+
+generate!(ActorRuntimeId, <>, u64);
+generate!(ActorUniqueId, <>, i64);
+
+/// Uuid represents a 128-bit universally unique identifier. It is written out by hand rather than
+/// with `generate!` as it is displayed in its hyphenated form instead of as an integer.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Uuid {
+    val: u128,
+}
+
+/// Uuid is serialized as its most significant half followed by its least significant half, each
+/// as a little endian u64.
+impl Binary for Uuid {
+    fn serialize(&self, buf: &mut Buffer) {
+        U64::<LE>::new((self.val >> 64) as u64).serialize(buf);
+        U64::<LE>::new(self.val as u64).serialize(buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Uuid", |buf| {
+            let msb = U64::<LE>::deserialize(buf)?.get() as u128;
+            let lsb = U64::<LE>::deserialize(buf)?.get() as u128;
+
+            Some(Self::new(msb << 64 | lsb))
+        })
+    }
+}
+
+impl_binary_ctx!(Uuid);
+
+impl Uuid {
+    pub const fn new(val: u128) -> Self {
+        Self { val }
+    }
+
+    pub const fn get(self) -> u128 {
+        self.val
+    }
+
+    /// Parses a UUID in its hyphenated form, e.g. `123e4567-e89b-12d3-a456-426614174000`.
+    /// Returns None if the string is not in that form.
+    pub fn parse(str: &str) -> Option<Self> {
+        let bytes = str.as_bytes();
+        if bytes.len() != 36 {
+            return None;
+        }
+
+        let mut val = 0u128;
+        for (i, &b) in bytes.iter().enumerate() {
+            match i {
+                8 | 13 | 18 | 23 if b == b'-' => continue,
+                8 | 13 | 18 | 23 => return None,
+                _ => val = val << 4 | (b as char).to_digit(16)? as u128,
+            }
+        }
+
+        Some(Self::new(val))
+    }
+
+    /// Returns the bytes of the UUID in the order of its textual form.
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.val.to_be_bytes()
+    }
+
+    /// Creates a UUID from bytes in the order of its textual form.
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self::new(u128::from_be_bytes(bytes))
+    }
+}
+
+impl Display for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let val = self.val;
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            val >> 96,
+            (val >> 80) & 0xffff,
+            (val >> 64) & 0xffff,
+            (val >> 48) & 0xffff,
+            val & 0xffff_ffff_ffff
+        )
+    }
+}
+
+impl core::fmt::Debug for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl From<u128> for Uuid {
+    fn from(val: u128) -> Self {
+        Self::new(val)
+    }
+}
+
+impl From<Uuid> for u128 {
+    fn from(uuid: Uuid) -> Self {
+        uuid.val
+    }
+}
+
+/// ActorRuntimeId identifies an actor for as long as it is loaded and is serialized as an
+/// unsigned varint.
+impl Binary for ActorRuntimeId {
+    fn serialize(&self, buf: &mut Buffer) {
+        W64::new(self.val).serialize(buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("ActorRuntimeId", |buf| {
+            let val = W64::deserialize(buf)?.get();
+            Some(Self::new(val))
+        })
+    }
+}
+
+impl_binary_ctx!(ActorRuntimeId);
+
+/// ActorUniqueId identifies an actor across saves and is serialized as a zigzag encoded varint.
+impl Binary for ActorUniqueId {
+    fn serialize(&self, buf: &mut Buffer) {
+        V64::new(self.val).serialize(buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("ActorUniqueId", |buf| {
+            let val = V64::deserialize(buf)?.get();
+            Some(Self::new(val))
+        })
+    }
+}
+
+impl_binary_ctx!(ActorUniqueId);
+
+mod tests {
+    ///
+    /// Tests the encoding and the textual form of UUIDs and the encoding of actor IDs.
+    ///
+    #[test]
+    pub fn identity() {
+        use crate::{ActorRuntimeId, ActorUniqueId, Binary, Buffer, Uuid};
+
+        let uuid = Uuid::parse("123e4567-e89b-12d3-a456-426614174000").unwrap();
+        assert_eq!(uuid.to_string(), "123e4567-e89b-12d3-a456-426614174000");
+        assert_eq!(Uuid::from_bytes(uuid.to_bytes()), uuid);
+        assert!(Uuid::parse("123e4567+e89b-12d3-a456-426614174000").is_none());
+        assert!(Uuid::parse("123e4567-e89b-12d3-a456-42661417400g").is_none());
+
+        let mut buf = Buffer::new(16);
+        uuid.serialize(&mut buf);
+        assert_eq!(&buf[..8], &0x123e4567e89b12d3u64.to_le_bytes());
+        assert_eq!(&buf[8..], &0xa456426614174000u64.to_le_bytes());
+
+        buf.set_offset(0);
+        assert_eq!(Uuid::deserialize(&mut buf), Some(uuid));
+
+        let mut buf = Buffer::new(3);
+        ActorRuntimeId::new(300).serialize(&mut buf);
+        ActorUniqueId::new(-1).serialize(&mut buf);
+        assert_eq!(&buf[..], &[0xac, 0x02, 0x01]);
+    }
+}
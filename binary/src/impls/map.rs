@@ -0,0 +1,297 @@
+use crate::{Binary, BinaryCtx, Buffer, Prefix};
+use alloc::collections::BTreeMap;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+/// Map serializes a keyed collection as a prefixed entry count followed by every key and value.
+/// It is written out by hand rather than with `generate!` because the wrapped type is a bare type
+/// parameter, for which the reverse `From` conversion cannot be implemented.
+#[repr(transparent)]
+pub struct Map<P: Prefix, M: Entries> {
+    val: M,
+    prefix: PhantomData<P>,
+}
+
+impl<P: Prefix, M: Entries> Map<P, M> {
+    pub fn new(val: M) -> Self {
+        Self {
+            val,
+            prefix: PhantomData,
+        }
+    }
+
+    pub fn get(self) -> M {
+        self.val
+    }
+}
+
+impl<P: Prefix, M: Entries> AsRef<M> for Map<P, M> {
+    fn as_ref(&self) -> &M {
+        &self.val
+    }
+}
+
+impl<P: Prefix, M: Entries> Debug for Map<P, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.val)
+    }
+}
+
+impl<P: Prefix, M: Entries + PartialEq> PartialEq for Map<P, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.val == other.val
+    }
+}
+
+impl<P: Prefix, M: Entries + Eq> Eq for Map<P, M> {}
+
+impl<P: Prefix, M: Entries> core::ops::Deref for Map<P, M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.val
+    }
+}
+
+impl<P: Prefix, M: Entries> core::ops::DerefMut for Map<P, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.val
+    }
+}
+
+impl<P: Prefix, M: Entries> From<M> for Map<P, M> {
+    fn from(value: M) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Entries is implemented by the collections that can back a [`Map`]. The collection decides the
+/// order in which entries are written: [`BTreeMap`] writes them sorted by key, `IndexMap` (behind
+/// the `indexmap` feature) in insertion order and `HashMap` (behind the `std` feature) in an
+/// unspecified order.
+pub trait Entries: Default + Debug {
+    type Key: Debug;
+    type Value: Debug;
+
+    /// Returns the number of entries in the collection.
+    fn len(&self) -> usize;
+
+    /// Returns true if the collection holds no entries.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the entries in the order they are to be serialized.
+    fn entries(&self) -> impl Iterator<Item = (&Self::Key, &Self::Value)>;
+
+    /// Inserts the entry unless the key is already present, returning false in that case.
+    fn insert_unique(&mut self, key: Self::Key, value: Self::Value) -> bool;
+}
+
+impl<K: Debug + Ord, V: Debug> Entries for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+
+    fn insert_unique(&mut self, key: K, value: V) -> bool {
+        match self.entry(key) {
+            alloc::collections::btree_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                true
+            }
+            alloc::collections::btree_map::Entry::Occupied(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Debug + Eq + core::hash::Hash, V: Debug> Entries for std::collections::HashMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+
+    fn insert_unique(&mut self, key: K, value: V) -> bool {
+        match self.entry(key) {
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                true
+            }
+            std::collections::hash_map::Entry::Occupied(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K: Debug + Eq + core::hash::Hash, V: Debug> Entries for indexmap::IndexMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+
+    fn insert_unique(&mut self, key: K, value: V) -> bool {
+        match self.entry(key) {
+            indexmap::map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                true
+            }
+            indexmap::map::Entry::Occupied(_) => false,
+        }
+    }
+}
+
+impl<P: Prefix, M: Entries> Binary for Map<P, M>
+where
+    M::Key: Binary,
+    M::Value: Binary,
+{
+    fn serialize(&self, buf: &mut Buffer) {
+        P::encode(self.val.len(), buf);
+        for (key, value) in self.val.entries() {
+            key.serialize(buf);
+            value.serialize(buf);
+        }
+    }
+
+    /// Deserializes the entries in the order they were written. A repeated key makes the whole
+    /// map invalid and returns None rather than silently overwriting the earlier value.
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Map", |buf| {
+            let len = P::decode(buf)?;
+            let mut map = M::default();
+
+            for _ in 0..len {
+                let key = M::Key::deserialize(buf)?;
+                let value = M::Value::deserialize(buf)?;
+                if !map.insert_unique(key, value) {
+                    return None;
+                }
+            }
+
+            Some(Self::new(map))
+        })
+    }
+
+    /// Skips the entries without checking for repeated keys, as that would require keeping them.
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        let len = P::decode(buf)?;
+        for _ in 0..len {
+            M::Key::skip(buf)?;
+            M::Value::skip(buf)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<C, P: Prefix, M: Entries> BinaryCtx<C> for Map<P, M>
+where
+    M::Key: BinaryCtx<C>,
+    M::Value: BinaryCtx<C>,
+{
+    fn serialize_ctx(&self, ctx: &C, buf: &mut Buffer) {
+        P::encode(self.val.len(), buf);
+        for (key, value) in self.val.entries() {
+            key.serialize_ctx(ctx, buf);
+            value.serialize_ctx(ctx, buf);
+        }
+    }
+
+    fn deserialize_ctx(ctx: &C, buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Map", |buf| {
+            let len = P::decode(buf)?;
+            let mut map = M::default();
+
+            for _ in 0..len {
+                let key = M::Key::deserialize_ctx(ctx, buf)?;
+                let value = M::Value::deserialize_ctx(ctx, buf)?;
+                if !map.insert_unique(key, value) {
+                    return None;
+                }
+            }
+
+            Some(Self::new(map))
+        })
+    }
+
+    fn skip_ctx(ctx: &C, buf: &mut Buffer) -> Option<()> {
+        let len = P::decode(buf)?;
+        for _ in 0..len {
+            M::Key::skip_ctx(ctx, buf)?;
+            M::Value::skip_ctx(ctx, buf)?;
+        }
+
+        Some(())
+    }
+}
+
+mod tests {
+    ///
+    /// Checks that maps round trip and that repeated keys are rejected.
+    ///
+    #[test]
+    pub fn map() {
+        use crate::{Binary, Buffer, CString, Map, U8, W32};
+        use std::collections::BTreeMap;
+
+        let mut rules = BTreeMap::new();
+        rules.insert(CString::<W32>::new("showcoordinates".into()), U8::new(1));
+        rules.insert(CString::<W32>::new("dodaylightcycle".into()), U8::new(0));
+
+        let map = Map::<W32, _>::new(rules);
+        let mut buf = Buffer::new(64);
+        map.serialize(&mut buf);
+        buf.set_offset(0);
+
+        let read = Map::<W32, BTreeMap<CString<W32>, U8>>::deserialize(&mut buf).unwrap();
+        assert_eq!(read, map);
+
+        let mut buf = Buffer::from(&[2, 1, 7, 1, 8][..]);
+        assert!(Map::<W32, BTreeMap<U8, U8>>::deserialize(&mut buf).is_none());
+    }
+
+    ///
+    /// Checks that an IndexMap writes its entries in insertion order rather than sorted by key,
+    /// and that the order survives a round trip.
+    ///
+    #[cfg(feature = "indexmap")]
+    #[test]
+    pub fn index_map() {
+        use crate::{Binary, Buffer, Map, U8, W32};
+        use indexmap::IndexMap;
+
+        let mut entries = IndexMap::new();
+        entries.insert(U8::new(3), U8::new(30));
+        entries.insert(U8::new(1), U8::new(10));
+        entries.insert(U8::new(2), U8::new(20));
+
+        let map = Map::<W32, _>::new(entries);
+        let mut buf = Buffer::new(7);
+        map.serialize(&mut buf);
+        assert_eq!(&buf[..], &[3, 3, 30, 1, 10, 2, 20]);
+        buf.set_offset(0);
+
+        let read = Map::<W32, IndexMap<U8, U8>>::deserialize(&mut buf).unwrap();
+        let keys = read.keys().map(|key| key.get()).collect::<Vec<_>>();
+        assert_eq!(keys, [3, 1, 2]);
+    }
+}
@@ -10,26 +10,44 @@ pub use string::*;
 pub mod sequence;
 pub use sequence::*;
 
+pub mod map;
+pub use map::*;
+
+pub mod geometry;
+pub use geometry::*;
+
+pub mod identity;
+pub use identity::*;
+
+pub mod raknet;
+pub use raknet::*;
+
+pub mod endian;
+
+pub mod enums;
+
+pub mod flags;
+pub use flags::*;
+
 ///
 /// This macro is used to generate the implementations for wrapping generic rust types to
 /// abstract away the serialization and deserialization over the wire.
 ///
-
 #[macro_export]
 macro_rules! generate {
     ($name:ident, <$($gen:ident: $gen_constraint:ident),*>, $type:ty $(,$lifetime:tt)?) => {
         #[allow(non_snake_case)]
-        #[derive(PartialEq)]
+        #[repr(transparent)]
         pub struct $name<$($lifetime,)? $($gen: $gen_constraint),*> {
             val: $type,
-            $( $gen_constraint: std::marker::PhantomData<$gen>, )*
+            $( $gen_constraint: core::marker::PhantomData<$gen>, )*
         }
 
         impl<$($lifetime,)? $($gen: $gen_constraint),*> $name<$($lifetime,)? $($gen),*> {
             pub fn new(val: $type) -> Self {
                 Self {
                     val,
-                    $( $gen_constraint: std::marker::PhantomData, )*
+                    $( $gen_constraint: core::marker::PhantomData, )*
                 }
             }
 
@@ -44,12 +62,92 @@ macro_rules! generate {
             }
         }
 
-        impl<$($lifetime,)? $($gen: $gen_constraint),*> std::fmt::Debug for $name<$($lifetime,)? $($gen),*> {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> core::fmt::Debug for $name<$($lifetime,)? $($gen),*> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 write!(f, "{:?}", self.val)
             }
         }
 
+        // The standard trait impls are bounded on the wrapped type only, so markers such as the
+        // prefix never need to implement them. The `for<'x>` binder defers the bound to the use
+        // site, so wrappers around types without these traits (e.g. floats) still compile.
+        #[allow(clippy::non_canonical_clone_impl)]
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> Clone for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: Clone,
+        {
+            fn clone(&self) -> Self {
+                Self::new(self.val.clone())
+            }
+        }
+
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> Copy for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: Copy,
+        {
+        }
+
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> Default for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: Default,
+        {
+            fn default() -> Self {
+                Self::new(Default::default())
+            }
+        }
+
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> core::fmt::Display for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: core::fmt::Display,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.val, f)
+            }
+        }
+
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> PartialEq for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: PartialEq,
+        {
+            fn eq(&self, other: &Self) -> bool {
+                self.val == other.val
+            }
+        }
+
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> Eq for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: Eq,
+        {
+        }
+
+        #[allow(clippy::non_canonical_partial_ord_impl)]
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> PartialOrd for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: PartialOrd,
+        {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                self.val.partial_cmp(&other.val)
+            }
+        }
+
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> Ord for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: Ord,
+        {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.val.cmp(&other.val)
+            }
+        }
+
+        impl<$($lifetime,)? $($gen: $gen_constraint),*> core::hash::Hash for $name<$($lifetime,)? $($gen),*>
+        where
+            for<'x> $type: core::hash::Hash,
+        {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.val.hash(state)
+            }
+        }
+
         impl<$($lifetime,)? $($gen: $gen_constraint),*> core::ops::Deref for $name<$($lifetime,)? $($gen),*> {
             type Target = $type;
 
@@ -1,14 +1,20 @@
 use crate::{generate, Binary, Buffer, ByteOrder};
+use alloc::{vec, vec::Vec};
 
 generate!(U8, <>, u8);
 generate!(I8, <>, i8);
 generate!(U16, <E: ByteOrder>, u16);
 generate!(I16, <E: ByteOrder>, i16);
 generate!(U24, <E: ByteOrder>, u32);
+generate!(I24, <E: ByteOrder>, i32);
 generate!(U32, <E: ByteOrder>, u32);
 generate!(I32, <E: ByteOrder>, i32);
+generate!(U48, <E: ByteOrder>, u64);
 generate!(U64, <E: ByteOrder>, u64);
 generate!(I64, <E: ByteOrder>, i64);
+generate!(U128, <E: ByteOrder>, u128);
+generate!(I128, <E: ByteOrder>, i128);
+generate!(F16, <E: ByteOrder>, f32);
 generate!(F32, <E: ByteOrder>, f32);
 generate!(F64, <E: ByteOrder>, f64);
 generate!(W32, <>, u32);
@@ -25,16 +31,40 @@ macro_rules! impl_unordered {
             }
 
             fn deserialize(buf: &mut Buffer) -> Option<Self> {
-                let mut bytes = [0u8; $n];
+                buf.trace(stringify!($wrapper), |buf| {
+                    let mut bytes = [0u8; $n];
 
-                if buf.read(&mut bytes) == $n {
-                    let val = <$ty>::from_le_bytes(bytes);
-                    return Some(Self::new(val));
-                };
+                    if buf.read(&mut bytes) == $n {
+                        let val = <$ty>::from_le_bytes(bytes);
+                        return Some(Self::new(val));
+                    };
 
-                None
+                    None
+                })
+            }
+
+            fn serialize_slice(vals: &[Self], buf: &mut Buffer) {
+                // SAFETY: The wrapper is transparent over the byte.
+                let bytes =
+                    unsafe { core::slice::from_raw_parts(vals.as_ptr() as *const u8, vals.len()) };
+                buf.write(bytes);
+            }
+
+            fn deserialize_vec(buf: &mut Buffer, len: usize) -> Option<Vec<Self>> {
+                if buf.remaining() < len {
+                    return None;
+                }
+
+                let bytes = buf.get(len, true);
+                Some(bytes.iter().map(|b| Self::new(*b as $ty)).collect())
+            }
+
+            fn skip_many(buf: &mut Buffer, len: usize) -> Option<()> {
+                buf.advance(len).then_some(())
             }
         }
+
+        crate::impl_binary_ctx!($wrapper);
     };
 }
 
@@ -42,158 +72,365 @@ impl_unordered!(U8, u8, 1);
 impl_unordered!(I8, i8, 1);
 
 macro_rules! impl_ordered {
-    ($wrapper:ident, $ty:ty, $read_method:ident, $write_method:ident) => {
+    (
+        $wrapper:ident, $ty:ty, $read_method:ident, $write_method:ident
+        $(; $read_slice:ident, $write_slice:ident)?
+    ) => {
         impl<E: ByteOrder> Binary for $wrapper<E> {
             fn serialize(&self, buf: &mut Buffer) {
                 E::$write_method(*self.as_ref(), buf)
             }
 
             fn deserialize(buf: &mut Buffer) -> Option<Self> {
-                let val = E::$read_method(buf)?;
-                Some(Self::new(val))
+                buf.trace(stringify!($wrapper), |buf| {
+                    let val = E::$read_method(buf)?;
+                    Some(Self::new(val))
+                })
             }
+
+            $(
+                fn serialize_slice(vals: &[Self], buf: &mut Buffer) {
+                    // SAFETY: The wrapper is transparent over the numeric type.
+                    let vals = unsafe {
+                        core::slice::from_raw_parts(vals.as_ptr() as *const $ty, vals.len())
+                    };
+                    E::$write_slice(vals, buf)
+                }
+
+                fn deserialize_vec(buf: &mut Buffer, len: usize) -> Option<Vec<Self>> {
+                    if buf.remaining() / core::mem::size_of::<$ty>() < len {
+                        return None;
+                    }
+
+                    let mut vec = vec![<$ty>::default(); len];
+                    E::$read_slice(buf, &mut vec)?;
+
+                    Some(vec.into_iter().map(Self::new).collect())
+                }
+
+                fn skip_many(buf: &mut Buffer, len: usize) -> Option<()> {
+                    let size = len.checked_mul(core::mem::size_of::<$ty>())?;
+                    buf.advance(size).then_some(())
+                }
+            )?
         }
+
+        crate::impl_binary_ctx!($wrapper<E: ByteOrder>);
     };
 }
 
-impl_ordered!(U16, u16, read_u16, write_u16);
-impl_ordered!(I16, i16, read_i16, write_i16);
+impl_ordered!(U16, u16, read_u16, write_u16; read_u16_slice, write_u16_slice);
+impl_ordered!(I16, i16, read_i16, write_i16; read_i16_slice, write_i16_slice);
 impl_ordered!(U24, u32, read_u24, write_u24);
-impl_ordered!(U32, u32, read_u32, write_u32);
-impl_ordered!(I32, i32, read_i32, write_i32);
-impl_ordered!(U64, u64, read_u64, write_u64);
-impl_ordered!(I64, i64, read_i64, write_i64);
-impl_ordered!(F32, f32, read_f32, write_f32);
-impl_ordered!(F64, f64, read_f64, write_f64);
-
-impl Binary for W32 {
-    fn serialize(&self, buf: &mut Buffer) {
-        let mut u = *self.as_ref();
-
-        while u >= 0x80 {
-            U8::new((u as u8) | 0x80).serialize(buf);
-            u >>= 7;
-        }
-
-        U8::new(u as u8).serialize(buf);
+impl_ordered!(I24, i32, read_i24, write_i24);
+impl_ordered!(U32, u32, read_u32, write_u32; read_u32_slice, write_u32_slice);
+impl_ordered!(I32, i32, read_i32, write_i32; read_i32_slice, write_i32_slice);
+impl_ordered!(U48, u64, read_u48, write_u48);
+impl_ordered!(U64, u64, read_u64, write_u64; read_u64_slice, write_u64_slice);
+impl_ordered!(I64, i64, read_i64, write_i64; read_i64_slice, write_i64_slice);
+impl_ordered!(U128, u128, read_u128, write_u128);
+impl_ordered!(I128, i128, read_i128, write_i128);
+impl_ordered!(F16, f32, read_f16, write_f16);
+impl_ordered!(F32, f32, read_f32, write_f32; read_f32_slice, write_f32_slice);
+impl_ordered!(F64, f64, read_f64, write_f64; read_f64_slice, write_f64_slice);
+
+/// Encodes the value as an unsigned varint into the array and returns the number of bytes used.
+#[inline]
+fn encode_varint(mut val: u64, bytes: &mut [u8; 10]) -> usize {
+    let mut i = 0;
+
+    while val >= 0x80 {
+        bytes[i] = (val as u8) | 0x80;
+        val >>= 7;
+        i += 1;
     }
 
-    fn deserialize(buf: &mut Buffer) -> Option<Self> {
-        let mut v: u32 = 0;
+    bytes[i] = val as u8;
+    i + 1
+}
 
-        for i in (0..35).step_by(7) {
-            let b = U8::deserialize(buf)?.get();
-            v |= ((b & 0x7f) as u32) << i;
+/// Decodes an unsigned varint of at most max bytes from the start of the array. Returns the value
+/// and the number of bytes it took or None if the varint does not end within max bytes.
+#[inline]
+fn decode_varint(bytes: &[u8; 10], max: usize) -> Option<(u64, usize)> {
+    let b = bytes[0];
+    if b < 0x80 {
+        return Some((b as u64, 1));
+    }
 
-            if b & 0x80 == 0 {
-                return Some(Self::new(v));
-            }
-        }
+    let mut val = (b & 0x7f) as u64;
+    for (i, b) in bytes.iter().enumerate().take(max).skip(1) {
+        val |= ((b & 0x7f) as u64) << (7 * i);
 
-        None
+        if b & 0x80 == 0 {
+            return Some((val, i + 1));
+        }
     }
+
+    None
 }
 
-impl Binary for V32 {
-    fn serialize(&self, buf: &mut Buffer) {
-        let u = *self.as_ref();
-        let mut ux = (u as u32) << 1;
+/// Reads an unsigned varint of at most max bytes from the buffer. The varint is decoded straight
+/// from the underlying slice if at least 10 bytes are left and byte by byte otherwise.
+#[inline]
+fn read_varint(buf: &mut Buffer, max: usize) -> Option<u64> {
+    if buf.remaining() >= 10 {
+        let bytes = buf.get(10, false).try_into().unwrap();
+        let (val, n) = decode_varint(bytes, max)?;
 
-        if u < 0 {
-            ux = !ux;
-        }
+        buf.advance(n);
+        return Some(val);
+    }
 
-        while ux >= 0x80 {
-            U8::new((ux as u8) | 0x80).serialize(buf);
-            ux >>= 7;
+    let mut val = 0;
+    for i in 0..max {
+        let mut b = [0u8];
+        if buf.read(&mut b) != 1 {
+            return None;
         }
 
-        U8::new(ux as u8).serialize(buf);
-    }
+        val |= ((b[0] & 0x7f) as u64) << (7 * i);
 
-    fn deserialize(buf: &mut Buffer) -> Option<Self> {
-        let mut ux: u32 = 0;
+        if b[0] & 0x80 == 0 {
+            return Some(val);
+        }
+    }
 
-        for i in (0..35).step_by(7) {
-            let b = U8::deserialize(buf)?.get();
-            ux |= ((b & 0x7f) as u32) << i;
+    None
+}
 
-            if b & 0x80 == 0 {
-                let mut x = (ux >> 1) as i32;
-                if ux & 1 != 0 {
-                    x = !x;
-                }
+macro_rules! impl_varint {
+    ($wrapper:ident, $max:expr, $encode:expr, $decode:expr) => {
+        impl Binary for $wrapper {
+            fn serialize(&self, buf: &mut Buffer) {
+                let mut bytes = [0u8; 10];
+                let n = encode_varint($encode(*self.as_ref()), &mut bytes);
 
-                return Some(Self::new(x));
+                buf.write(&bytes[..n]);
             }
-        }
 
-        None
-    }
-}
+            fn deserialize(buf: &mut Buffer) -> Option<Self> {
+                buf.trace(stringify!($wrapper), |buf| {
+                    let val = read_varint(buf, $max)?;
+                    Some(Self::new($decode(val)))
+                })
+            }
 
-impl Binary for W64 {
-    fn serialize(&self, buf: &mut Buffer) {
-        let mut u = *self.as_ref();
+            /// Decodes a run of varints straight from the underlying slice for as long as at
+            /// least 10 bytes are left, which is the case for all but the last few varints.
+            fn deserialize_vec(buf: &mut Buffer, len: usize) -> Option<Vec<Self>> {
+                let mut vec = Vec::with_capacity(len.min(buf.remaining()));
 
-        while u >= 0x80 {
-            U8::new((u as u8) | 0x80).serialize(buf);
-            u >>= 7;
-        }
+                let bytes = &buf[buf.offset()..];
+                let mut pos = 0;
 
-        U8::new(u as u8).serialize(buf);
-    }
+                while vec.len() < len && bytes.len() - pos >= 10 {
+                    let (val, n) = decode_varint(bytes[pos..pos + 10].try_into().unwrap(), $max)?;
 
-    fn deserialize(buf: &mut Buffer) -> Option<Self> {
-        let mut v: u64 = 0;
+                    vec.push(Self::new($decode(val)));
+                    pos += n;
+                }
 
-        for i in (0..70).step_by(7) {
-            let b = U8::deserialize(buf)?.get();
-            v |= ((b & 0x7f) as u64) << i;
+                buf.advance(pos);
+                while vec.len() < len {
+                    let val = read_varint(buf, $max)?;
+                    vec.push(Self::new($decode(val)));
+                }
 
-            if b & 0x80 == 0 {
-                return Some(Self::new(v));
+                Some(vec)
             }
         }
 
-        None
-    }
+        crate::impl_binary_ctx!($wrapper);
+    };
 }
 
-impl Binary for V64 {
-    fn serialize(&self, buf: &mut Buffer) {
-        let u = *self.as_ref();
-        let mut ux = (u as u32) << 1;
+impl_varint!(W32, 5, |v: u32| v as u64, |v: u64| v as u32);
+impl_varint!(
+    V32,
+    5,
+    |v: i32| ((v << 1) ^ (v >> 31)) as u32 as u64,
+    |v: u64| ((v as u32 >> 1) as i32) ^ -((v & 1) as i32)
+);
+impl_varint!(W64, 10, |v: u64| v, |v: u64| v);
+impl_varint!(
+    V64,
+    10,
+    |v: i64| ((v << 1) ^ (v >> 63)) as u64,
+    |v: u64| ((v >> 1) as i64) ^ -((v & 1) as i64)
+);
+
+macro_rules! impl_binop {
+    (
+        $wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>,
+        $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident
+    ) => {
+        impl<$($gen: $gen_constraint),*> core::ops::$trait for $wrapper<$($gen),*> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                Self::new(core::ops::$trait::$method(self.val, rhs.val))
+            }
+        }
 
-        if u < 0 {
-            ux = !ux;
+        impl<$($gen: $gen_constraint),*> core::ops::$assign_trait for $wrapper<$($gen),*> {
+            fn $assign_method(&mut self, rhs: Self) {
+                core::ops::$assign_trait::$assign_method(&mut self.val, rhs.val)
+            }
         }
+    };
+}
 
-        while ux >= 0x80 {
-            U8::new((ux as u8) | 0x80).serialize(buf);
-            ux >>= 7;
+macro_rules! impl_unop {
+    ($wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>, $trait:ident, $method:ident) => {
+        impl<$($gen: $gen_constraint),*> core::ops::$trait for $wrapper<$($gen),*> {
+            type Output = Self;
+
+            fn $method(self) -> Self {
+                Self::new(core::ops::$trait::$method(self.val))
+            }
         }
+    };
+}
 
-        U8::new(ux as u8).serialize(buf);
-    }
+/// Forwards the arithmetic operators of the numeric type to its wrapper. The operators behave
+/// exactly like those of the numeric type, including on overflow.
+macro_rules! impl_arith {
+    (float $wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>) => {
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Add, add, AddAssign, add_assign);
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Sub, sub, SubAssign, sub_assign);
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Mul, mul, MulAssign, mul_assign);
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Div, div, DivAssign, div_assign);
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Rem, rem, RemAssign, rem_assign);
+        impl_unop!($wrapper, <$($gen: $gen_constraint),*>, Neg, neg);
+    };
+    (unsigned $wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>) => {
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Add, add, AddAssign, add_assign);
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Sub, sub, SubAssign, sub_assign);
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Mul, mul, MulAssign, mul_assign);
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Div, div, DivAssign, div_assign);
+        impl_binop!($wrapper, <$($gen: $gen_constraint),*>, Rem, rem, RemAssign, rem_assign);
+        impl_binop!(
+            $wrapper, <$($gen: $gen_constraint),*>, BitAnd, bitand, BitAndAssign, bitand_assign
+        );
+        impl_binop!(
+            $wrapper, <$($gen: $gen_constraint),*>, BitOr, bitor, BitOrAssign, bitor_assign
+        );
+        impl_binop!(
+            $wrapper, <$($gen: $gen_constraint),*>, BitXor, bitxor, BitXorAssign, bitxor_assign
+        );
+        impl_unop!($wrapper, <$($gen: $gen_constraint),*>, Not, not);
+    };
+    (signed $wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>) => {
+        impl_arith!(unsigned $wrapper, <$($gen: $gen_constraint),*>);
+        impl_unop!($wrapper, <$($gen: $gen_constraint),*>, Neg, neg);
+    };
+}
 
-    fn deserialize(buf: &mut Buffer) -> Option<Self> {
-        let mut ux: u64 = 0;
+impl_arith!(unsigned U8, <>);
+impl_arith!(signed I8, <>);
+impl_arith!(unsigned U16, <E: ByteOrder>);
+impl_arith!(signed I16, <E: ByteOrder>);
+impl_arith!(unsigned U24, <E: ByteOrder>);
+impl_arith!(signed I24, <E: ByteOrder>);
+impl_arith!(unsigned U32, <E: ByteOrder>);
+impl_arith!(signed I32, <E: ByteOrder>);
+impl_arith!(unsigned U48, <E: ByteOrder>);
+impl_arith!(unsigned U64, <E: ByteOrder>);
+impl_arith!(signed I64, <E: ByteOrder>);
+impl_arith!(unsigned U128, <E: ByteOrder>);
+impl_arith!(signed I128, <E: ByteOrder>);
+impl_arith!(float F16, <E: ByteOrder>);
+impl_arith!(float F32, <E: ByteOrder>);
+impl_arith!(float F64, <E: ByteOrder>);
+impl_arith!(unsigned W32, <>);
+impl_arith!(signed V32, <>);
+impl_arith!(unsigned W64, <>);
+impl_arith!(signed V64, <>);
+
+mod tests {
+    ///
+    /// Tests the standard traits and operators of the numeric wrappers.
+    ///
+    #[test]
+    pub fn traits() {
+        use crate::{CString, F32, LE, U32, V32, W32};
+        use std::collections::{BTreeSet, HashSet};
+
+        let a = U32::<LE>::new(40);
+        let mut b = a;
+        b += U32::new(2);
+        assert_eq!((a + b).get(), 82);
+        assert_eq!((b & U32::new(0xf)).get(), 10);
+        assert_eq!((-V32::new(3)).get(), -3);
+        assert_eq!((F32::<LE>::new(1.5) * F32::new(2.0)).get(), 3.0);
+        assert_eq!(W32::default().to_string(), "0");
+        assert!(a < b);
+
+        let names: BTreeSet<_> = ["b", "a"].map(|s| CString::<W32>::new(s.into())).into();
+        assert_eq!(names.first().map(ToString::to_string).as_deref(), Some("a"));
+
+        let set: HashSet<_> = names.iter().cloned().collect();
+        assert!(set.contains(&CString::new("b".into())));
+    }
 
-        for i in (0..70).step_by(7) {
-            let b = U8::deserialize(buf)?.get();
-            ux |= ((b & 0x7f) as u64) << i;
+    ///
+    /// Tests that varints round trip through both the fast path and the byte by byte path, and
+    /// through the batch decoder.
+    ///
+    #[test]
+    pub fn varint() {
+        use crate::{Binary, Buffer, V32, V64, W32, W64};
+
+        let signed = [
+            0,
+            1,
+            -1,
+            63,
+            -64,
+            64,
+            i32::MAX as i64,
+            i32::MIN as i64,
+            i64::MAX,
+            i64::MIN,
+        ];
+        let unsigned = [0, 1, 127, 128, 16383, 16384, u32::MAX as u64, u64::MAX];
+
+        for pad in [0, 10] {
+            let mut buffer = Buffer::new(256 + pad);
+            for v in signed {
+                V32::new(v as i32).serialize(&mut buffer);
+                V64::new(v).serialize(&mut buffer);
+            }
+            for v in unsigned {
+                W32::new(v as u32).serialize(&mut buffer);
+                W64::new(v).serialize(&mut buffer);
+            }
 
-            if b & 0x80 == 0 {
-                let mut x = (ux >> 1) as i64;
-                if ux & 1 != 0 {
-                    x = !x;
-                }
+            let len = buffer.offset();
+            buffer.set_offset(0);
+            buffer.resize(len + pad);
 
-                return Some(Self::new(x));
+            for v in signed {
+                assert_eq!(V32::deserialize(&mut buffer).unwrap().get(), v as i32);
+                assert_eq!(V64::deserialize(&mut buffer).unwrap().get(), v);
+            }
+            for v in unsigned {
+                assert_eq!(W32::deserialize(&mut buffer).unwrap().get(), v as u32);
+                assert_eq!(W64::deserialize(&mut buffer).unwrap().get(), v);
             }
         }
 
-        None
+        let mut buffer = Buffer::new(1024);
+        for v in 0..200 {
+            V32::new(v * 1000 - 50000).serialize(&mut buffer);
+        }
+
+        buffer.set_offset(0);
+        let vec = V32::deserialize_vec(&mut buffer, 200).unwrap();
+        assert!(vec
+            .into_iter()
+            .map(V32::get)
+            .eq((0..200).map(|v| v * 1000 - 50000)));
     }
 }
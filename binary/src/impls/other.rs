@@ -1,6 +1,10 @@
-use crate::{generate, Binary, Buffer, U8};
+use crate::{generate, impl_binary_ctx, Binary, BinaryCtx, Buffer, Presence, U8};
+use core::fmt::Debug;
 
 generate!(Bool, <>, bool);
+// The value of an Optional is only bounded by Debug so that it may be context-aware.
+generate!(Optional, <M: Presence, B: Debug>, Option<B>);
+generate!(Conditional, <B: Binary>, Option<B>);
 
 impl Binary for Bool {
     fn serialize(&self, buf: &mut Buffer) {
@@ -9,13 +13,216 @@ impl Binary for Bool {
     }
 
     fn deserialize(buf: &mut Buffer) -> Option<Self> {
-        let val = U8::deserialize(buf)?;
-        let b = match val.get() {
-            0x01 => true,
-            0x00 => false,
-            v => panic!("Unable to deBinary the value of bool from value {}", v),
-        };
-
-        Some(Self::new(b))
+        buf.trace("Bool", |buf| {
+            let val = U8::deserialize(buf)?;
+            let b = match val.get() {
+                0x01 => true,
+                0x00 => false,
+                _ => return None,
+            };
+
+            Some(Self::new(b))
+        })
+    }
+}
+
+impl_binary_ctx!(Bool);
+
+impl<M: Presence, B: Binary> Binary for Optional<M, B> {
+    fn serialize(&self, buf: &mut Buffer) {
+        M::encode(self.is_some(), buf);
+
+        if let Some(val) = self.as_ref() {
+            val.serialize(buf);
+        }
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Optional", |buf| {
+            let val = match M::decode(buf)? {
+                true => Some(B::deserialize(buf)?),
+                false => None,
+            };
+
+            Some(Self::new(val))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        match M::decode(buf)? {
+            true => B::skip(buf),
+            false => Some(()),
+        }
+    }
+}
+
+impl<C, M: Presence, B: BinaryCtx<C>> BinaryCtx<C> for Optional<M, B> {
+    fn serialize_ctx(&self, ctx: &C, buf: &mut Buffer) {
+        M::encode(self.is_some(), buf);
+
+        if let Some(val) = self.as_ref() {
+            val.serialize_ctx(ctx, buf);
+        }
+    }
+
+    fn deserialize_ctx(ctx: &C, buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Optional", |buf| {
+            let val = match M::decode(buf)? {
+                true => Some(B::deserialize_ctx(ctx, buf)?),
+                false => None,
+            };
+
+            Some(Self::new(val))
+        })
+    }
+
+    fn skip_ctx(ctx: &C, buf: &mut Buffer) -> Option<()> {
+        match M::decode(buf)? {
+            true => B::skip_ctx(ctx, buf),
+            false => Some(()),
+        }
+    }
+}
+
+/// The context of a Conditional is whether the value is present, which is expected to be
+/// serialized separately by an earlier field, such as a flag bit.
+impl<B: Binary> BinaryCtx<bool> for Conditional<B> {
+    fn serialize_ctx(&self, _: &bool, buf: &mut Buffer) {
+        if let Some(val) = self.as_ref() {
+            val.serialize(buf);
+        }
+    }
+
+    fn deserialize_ctx(present: &bool, buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Conditional", |buf| {
+            let val = match present {
+                true => Some(B::deserialize(buf)?),
+                false => None,
+            };
+
+            Some(Self::new(val))
+        })
+    }
+
+    fn skip_ctx(present: &bool, buf: &mut Buffer) -> Option<()> {
+        match present {
+            true => B::skip(buf),
+            false => Some(()),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),+) => {
+        #[allow(non_snake_case)]
+        impl<$($name: Binary),+> Binary for ($($name,)+) {
+            fn serialize(&self, buf: &mut Buffer) {
+                let ($($name,)+) = self;
+                $($name.serialize(buf);)+
+            }
+
+            fn deserialize(buf: &mut Buffer) -> Option<Self> {
+                Some(($($name::deserialize(buf)?,)+))
+            }
+
+            fn skip(buf: &mut Buffer) -> Option<()> {
+                $($name::skip(buf)?;)+
+                Some(())
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<Ctx, $($name: BinaryCtx<Ctx>),+> BinaryCtx<Ctx> for ($($name,)+) {
+            fn serialize_ctx(&self, ctx: &Ctx, buf: &mut Buffer) {
+                let ($($name,)+) = self;
+                $($name.serialize_ctx(ctx, buf);)+
+            }
+
+            fn deserialize_ctx(ctx: &Ctx, buf: &mut Buffer) -> Option<Self> {
+                Some(($($name::deserialize_ctx(ctx, buf)?,)+))
+            }
+
+            fn skip_ctx(ctx: &Ctx, buf: &mut Buffer) -> Option<()> {
+                $($name::skip_ctx(ctx, buf)?;)+
+                Some(())
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, H);
+impl_tuple!(A, B, C, D, E, F, G, H, I);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+mod tests {
+    ///
+    /// Tests that tuples serialize their fields in order and fail as a whole if any field fails.
+    ///
+    #[test]
+    pub fn tuple() {
+        use crate::{Binary, Buffer, CString, I32, LE, U8, W32};
+
+        type Field = (U8, CString<W32>, I32<LE>);
+
+        let mut buf = Buffer::new(8);
+        let field: Field = (U8::new(1), CString::new("ab".into()), I32::new(-2));
+        field.serialize(&mut buf);
+        assert_eq!(&buf[..], &[1, 2, b'a', b'b', 0xfe, 0xff, 0xff, 0xff]);
+
+        buf.set_offset(0);
+        assert_eq!(Field::deserialize(&mut buf).unwrap(), field);
+
+        buf.set_offset(0);
+        buf.resize(7);
+        assert!(Field::deserialize(&mut buf).is_none());
+    }
+
+    ///
+    /// Tests that optional values round trip whether they are present or not, with their presence
+    /// serialized by a marker or supplied by an earlier flag, and that invalid markers are rejected.
+    ///
+    #[test]
+    pub fn optional() {
+        use crate::{Binary, BinaryCtx, Bool, Buffer, Conditional, Optional, I32, LE, U8};
+
+        for val in [Some(I32::<LE>::new(-2)), None] {
+            let optional = Optional::<Bool, I32<LE>>::new(val);
+            let conditional = Conditional::new(val);
+
+            let mut buf = Buffer::new(16);
+            optional.serialize(&mut buf);
+            conditional.serialize_ctx(&val.is_some(), &mut buf);
+            let len = buf.offset();
+            assert_eq!(len, if val.is_some() { 9 } else { 1 });
+
+            buf.set_offset(0);
+            buf.resize(len);
+            assert_eq!(Optional::deserialize(&mut buf).unwrap(), optional);
+            assert_eq!(
+                Conditional::deserialize_ctx(&val.is_some(), &mut buf).unwrap(),
+                conditional
+            );
+            assert_eq!(buf.remaining(), 0);
+
+            buf.set_offset(0);
+            Optional::<Bool, I32<LE>>::skip(&mut buf).unwrap();
+            Conditional::<I32<LE>>::skip_ctx(&val.is_some(), &mut buf).unwrap();
+            assert_eq!(buf.remaining(), 0);
+        }
+
+        let mut buf = Buffer::from(vec![0x02, 0x01]);
+        assert!(Bool::deserialize(&mut buf).is_none());
+
+        buf.set_offset(0);
+        assert!(Optional::<Bool, U8>::deserialize(&mut buf).is_none());
     }
 }
@@ -0,0 +1,209 @@
+use crate::{generate, impl_binary_ctx, Binary, Buffer, BE, LE, U16, U24, U32, U64, U8};
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use core::time::Duration;
+
+generate!(SystemAddress, <>, SocketAddr);
+generate!(Sequence, <>, u32);
+generate!(Timestamp, <>, u64);
+
+/// The bytes every RakNet offline message carries to tell it apart from random datagrams.
+pub const MAGIC: [u8; 16] = [
+    0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78,
+];
+
+/// Magic is serialized as the [`MAGIC`] bytes. Deserialization fails unless the bytes match
+/// exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Magic;
+
+/// The address family written for IPv6 addresses, the value of AF_INET6 on Windows.
+const AF_INET6: u16 = 23;
+
+impl Binary for Magic {
+    fn serialize(&self, buf: &mut Buffer) {
+        buf.write(&MAGIC);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Magic", |buf| {
+            if buf.remaining() < MAGIC.len() || buf.get(MAGIC.len(), true) != MAGIC {
+                return None;
+            }
+
+            Some(Self)
+        })
+    }
+}
+
+impl_binary_ctx!(Magic);
+
+/// SystemAddress is serialized as a version byte followed by the address. IPv4 addresses are
+/// written as the bitwise inverted octets and a big endian port. IPv6 addresses are written in
+/// the layout of a sockaddr_in6: family, port, flow info, address and scope ID.
+impl Binary for SystemAddress {
+    fn serialize(&self, buf: &mut Buffer) {
+        match self.val {
+            SocketAddr::V4(addr) => {
+                U8::new(4).serialize(buf);
+                buf.write(&addr.ip().octets().map(|b| !b));
+                U16::<BE>::new(addr.port()).serialize(buf);
+            }
+            SocketAddr::V6(addr) => {
+                U8::new(6).serialize(buf);
+                U16::<LE>::new(AF_INET6).serialize(buf);
+                U16::<BE>::new(addr.port()).serialize(buf);
+                U32::<BE>::new(addr.flowinfo()).serialize(buf);
+                buf.write(&addr.ip().octets());
+                U32::<BE>::new(addr.scope_id()).serialize(buf);
+            }
+        }
+    }
+
+    /// Returns None if the version is neither 4 nor 6. The family of IPv6 addresses is not
+    /// checked as it depends on the platform of the sender.
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("SystemAddress", |buf| match U8::deserialize(buf)?.get() {
+            4 => {
+                let octets = <[U8; 4]>::deserialize(buf)?.map(|b| !b.get());
+                let port = U16::<BE>::deserialize(buf)?.get();
+
+                let addr = SocketAddrV4::new(Ipv4Addr::from(octets), port);
+                Some(Self::new(SocketAddr::V4(addr)))
+            }
+            6 => {
+                U16::<LE>::deserialize(buf)?;
+                let port = U16::<BE>::deserialize(buf)?.get();
+                let flowinfo = U32::<BE>::deserialize(buf)?.get();
+                let octets = <[U8; 16]>::deserialize(buf)?.map(U8::get);
+                let scope_id = U32::<BE>::deserialize(buf)?.get();
+
+                let addr = SocketAddrV6::new(Ipv6Addr::from(octets), port, flowinfo, scope_id);
+                Some(Self::new(SocketAddr::V6(addr)))
+            }
+            _ => None,
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        let len = match U8::deserialize(buf)?.get() {
+            4 => 6,
+            6 => 28,
+            _ => return None,
+        };
+
+        buf.advance(len).then_some(())
+    }
+}
+
+impl_binary_ctx!(SystemAddress);
+
+impl Sequence {
+    /// The number of distinct sequence numbers, after which they wrap around to zero.
+    pub const MODULUS: u32 = 1 << 24;
+
+    /// Returns the sequence number following this one.
+    pub fn next(&self) -> Self {
+        self.wrapping_add(1)
+    }
+
+    /// Adds to the sequence number, wrapping around at 24 bits.
+    pub fn wrapping_add(&self, n: u32) -> Self {
+        Self::new(self.val.wrapping_add(n) % Self::MODULUS)
+    }
+
+    /// Returns true if this sequence number comes after the other one, taking the wrap around
+    /// into account. Numbers less than half of the range ahead are considered newer.
+    pub fn is_after(&self, other: &Self) -> bool {
+        let diff = self.val.wrapping_sub(other.val) % Self::MODULUS;
+        diff != 0 && diff < Self::MODULUS / 2
+    }
+}
+
+/// Sequence is serialized as a little endian u24, as used for the sequence, message and ordering
+/// indices of RakNet frames. Bits above the 24th are not written.
+impl Binary for Sequence {
+    fn serialize(&self, buf: &mut Buffer) {
+        U24::<LE>::new(self.val % Self::MODULUS).serialize(buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Sequence", |buf| {
+            let val = U24::<LE>::deserialize(buf)?.get();
+            Some(Self::new(val))
+        })
+    }
+}
+
+impl_binary_ctx!(Sequence);
+
+impl Timestamp {
+    /// Returns the timestamp as the time elapsed since the start of the clock of the sender.
+    pub fn as_duration(&self) -> Duration {
+        Duration::from_millis(self.val)
+    }
+}
+
+impl From<Duration> for Timestamp {
+    fn from(duration: Duration) -> Self {
+        Self::new(duration.as_millis() as u64)
+    }
+}
+
+/// Timestamp is serialized as a big endian u64 holding milliseconds.
+impl Binary for Timestamp {
+    fn serialize(&self, buf: &mut Buffer) {
+        U64::<BE>::new(self.val).serialize(buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Timestamp", |buf| {
+            let val = U64::<BE>::deserialize(buf)?.get();
+            Some(Self::new(val))
+        })
+    }
+}
+
+impl_binary_ctx!(Timestamp);
+
+mod tests {
+    ///
+    /// Tests the magic validation, both address layouts and the wrap around of sequence numbers.
+    ///
+    #[test]
+    pub fn raknet() {
+        use crate::{Binary, Buffer, Magic, Sequence, SystemAddress, MAGIC};
+
+        let mut buf = Buffer::from(&MAGIC[..]);
+        assert_eq!(Magic::deserialize(&mut buf), Some(Magic));
+
+        let mut bytes = MAGIC;
+        bytes[15] = 0;
+        assert_eq!(Magic::deserialize(&mut Buffer::from(&bytes)), None);
+        assert_eq!(Magic::deserialize(&mut Buffer::from(&MAGIC[..8])), None);
+
+        let addr = SystemAddress::new("127.0.0.1:19132".parse().unwrap());
+        let mut buf = Buffer::new(7);
+        addr.serialize(&mut buf);
+        assert_eq!(&buf[..], &[4, 0x80, 0xff, 0xff, 0xfe, 0x4a, 0xbc]);
+
+        buf.set_offset(0);
+        assert_eq!(SystemAddress::deserialize(&mut buf), Some(addr));
+
+        let addr = SystemAddress::new("[::1]:19133".parse().unwrap());
+        let mut buf = Buffer::new(29);
+        addr.serialize(&mut buf);
+        assert_eq!(buf.remaining(), 0);
+
+        buf.set_offset(0);
+        assert_eq!(SystemAddress::deserialize(&mut buf), Some(addr));
+
+        let last = Sequence::new(Sequence::MODULUS - 1);
+        assert_eq!(last.next(), Sequence::new(0));
+        assert!(last.next().is_after(&last));
+        assert!(!last.is_after(&last.next()));
+
+        let mut buf = Buffer::new(3);
+        last.serialize(&mut buf);
+        assert_eq!(&buf[..], &[0xff, 0xff, 0xff]);
+    }
+}
@@ -1,42 +1,307 @@
-use crate::{generate, Binary, Buffer, Prefix};
+use crate::{generate, impl_binary_ctx, Binary, BinaryCtx, Buffer, Prefix};
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
-generate!(Array, <P: Prefix, B: Binary>, Vec<B>);
-generate!(RemBuf, <>, Vec<u8>);
+// The elements are only bounded by Debug so that the containers may hold context-aware elements,
+// which implement BinaryCtx rather than Binary.
+generate!(Array, <P: Prefix, B: Debug>, Vec<B>);
+generate!(RemBuf, <P: Prefix>, Vec<u8>);
+generate!(Counted, <B: Debug>, Vec<B>);
 
 impl<P: Prefix, B: Binary> Binary for Array<P, B> {
     fn serialize(&self, buf: &mut Buffer) {
         P::encode(self.len(), buf);
-
-        for val in self.iter() {
-            val.serialize(buf);
-        }
+        B::serialize_slice(self, buf);
     }
 
     fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Array", |buf| {
+            let len = P::decode(buf)?;
+            let vec = B::deserialize_vec(buf, len)?;
+
+            Some(Self::new(vec))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
         let len = P::decode(buf)?;
-        let mut vec = Vec::with_capacity(len);
+        B::skip_many(buf, len)
+    }
+}
 
-        for _ in 0..len {
-            vec.push(B::deserialize(buf)?);
-        }
+impl<C, P: Prefix, B: BinaryCtx<C>> BinaryCtx<C> for Array<P, B> {
+    fn serialize_ctx(&self, ctx: &C, buf: &mut Buffer) {
+        P::encode(self.len(), buf);
+        B::serialize_slice_ctx(self, ctx, buf);
+    }
 
-        Some(Self::new(vec))
+    fn deserialize_ctx(ctx: &C, buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Array", |buf| {
+            let len = P::decode(buf)?;
+            let vec = B::deserialize_vec_ctx(ctx, buf, len)?;
+
+            Some(Self::new(vec))
+        })
+    }
+
+    fn skip_ctx(ctx: &C, buf: &mut Buffer) -> Option<()> {
+        let len = P::decode(buf)?;
+        B::skip_many_ctx(ctx, buf, len)
     }
 }
 
 impl<P: Prefix> Binary for RemBuf<P> {
     fn serialize(&self, buf: &mut Buffer) {
         P::encode(self.len(), buf);
-        buf.write(&self);
+        buf.write(self);
     }
 
     fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("RemBuf", |buf| {
+            let len = P::decode(buf)?;
+            if buf.remaining() < len {
+                return None;
+            }
+
+            Some(Self::new(buf.get(len, true).to_vec()))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
         let len = P::decode(buf)?;
-        let start = buf.position() as usize;
-        let end = start + len;
+        buf.advance(len).then_some(())
+    }
+}
+
+impl_binary_ctx!(RemBuf<P: Prefix>);
+
+impl<B: Binary> Counted<B> {
+    /// Deserializes len elements where the length is supplied by an earlier field.
+    pub fn deserialize_len(buf: &mut Buffer, len: usize) -> Option<Self> {
+        let vec = B::deserialize_vec(buf, len)?;
+        Some(Self::new(vec))
+    }
+
+    /// Skips len elements where the length is supplied by an earlier field.
+    pub fn skip_len(buf: &mut Buffer, len: usize) -> Option<()> {
+        B::skip_many(buf, len)
+    }
+}
+
+impl<B: Binary> Binary for Counted<B> {
+    /// Serializes every element without a length prefix. The number of elements is expected to
+    /// be serialized separately by an earlier field.
+    fn serialize(&self, buf: &mut Buffer) {
+        B::serialize_slice(self, buf);
+    }
+
+    /// Deserializes elements until the end of the buffer, which is meant for regions whose length
+    /// in bytes is supplied by an earlier field through [`Buffer::take`]. See
+    /// [`Counted::deserialize_len`] for fields that supply the number of elements instead.
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Counted", |buf| {
+            let mut vec = Vec::new();
+
+            while buf.remaining() != 0 {
+                let offset = buf.offset();
+                vec.push(B::deserialize(buf)?);
+
+                // An element that consumes nothing would never reach the end of the buffer.
+                if buf.offset() == offset {
+                    return None;
+                }
+            }
+
+            Some(Self::new(vec))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        while buf.remaining() != 0 {
+            let offset = buf.offset();
+            B::skip(buf)?;
+
+            if buf.offset() == offset {
+                return None;
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl<C, B: BinaryCtx<C>> BinaryCtx<C> for Counted<B> {
+    fn serialize_ctx(&self, ctx: &C, buf: &mut Buffer) {
+        B::serialize_slice_ctx(self, ctx, buf);
+    }
+
+    fn deserialize_ctx(ctx: &C, buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Counted", |buf| {
+            let mut vec = Vec::new();
+
+            while buf.remaining() != 0 {
+                let offset = buf.offset();
+                vec.push(B::deserialize_ctx(ctx, buf)?);
+
+                if buf.offset() == offset {
+                    return None;
+                }
+            }
+
+            Some(Self::new(vec))
+        })
+    }
+
+    fn skip_ctx(ctx: &C, buf: &mut Buffer) -> Option<()> {
+        while buf.remaining() != 0 {
+            let offset = buf.offset();
+            B::skip_ctx(ctx, buf)?;
+
+            if buf.offset() == offset {
+                return None;
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl<B: Binary, const N: usize> Binary for [B; N] {
+    fn serialize(&self, buf: &mut Buffer) {
+        B::serialize_slice(self, buf);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        let vec = B::deserialize_vec(buf, N)?;
+        vec.try_into().ok()
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        B::skip_many(buf, N)
+    }
+}
+
+impl<C, B: BinaryCtx<C>, const N: usize> BinaryCtx<C> for [B; N] {
+    fn serialize_ctx(&self, ctx: &C, buf: &mut Buffer) {
+        B::serialize_slice_ctx(self, ctx, buf);
+    }
+
+    fn deserialize_ctx(ctx: &C, buf: &mut Buffer) -> Option<Self> {
+        let vec = B::deserialize_vec_ctx(ctx, buf, N)?;
+        vec.try_into().ok()
+    }
+
+    fn skip_ctx(ctx: &C, buf: &mut Buffer) -> Option<()> {
+        B::skip_many_ctx(ctx, buf, N)
+    }
+}
+
+mod tests {
+    ///
+    /// Tests that skipping lands on the same offset as deserializing and that malformed framing
+    /// is still detected.
+    ///
+    #[test]
+    pub fn skip() {
+        use crate::{Array, Binary, Buffer, CString, I32, LE, U8, V64, W32};
+
+        type Field = (Array<W32, CString<W32>>, Array<W32, I32<LE>>, V64);
+
+        let mut buf = Buffer::new(32);
+        let field: Field = (
+            Array::new(vec![CString::new("a".into()), CString::new("bc".into())]),
+            Array::new(vec![I32::new(1), I32::new(-1)]),
+            V64::new(i64::MIN),
+        );
+        field.serialize(&mut buf);
+        U8::new(7).serialize(&mut buf);
+
+        let end = buf.offset();
+        buf.set_offset(0);
+        assert_eq!(Field::skip(&mut buf), Some(()));
+        assert_eq!(U8::deserialize(&mut buf).map(U8::get), Some(7));
+        assert_eq!(buf.offset(), end);
+
+        let mut buf = Buffer::from(&[3, 1, b'a'][..]);
+        assert_eq!(Array::<W32, CString<W32>>::skip(&mut buf), None);
+
+        let mut buf = Buffer::from(&[0xff; 10][..]);
+        assert_eq!(V64::skip(&mut buf), None);
+    }
+
+    ///
+    /// Tests fixed-size arrays and counted sequences against known bytes, reading a counted
+    /// sequence both with a count supplied by an earlier field and nested in a bounded region.
+    ///
+    #[test]
+    pub fn counted() {
+        use crate::{Binary, Buffer, Counted, BE, U16, U8};
+
+        let mut buf = Buffer::new(4);
+        [U16::<BE>::new(1), U16::new(0x0203)].serialize(&mut buf);
+        assert_eq!(&buf[..], &[0, 1, 2, 3]);
+
+        buf.set_offset(0);
+        let array = <[U16<BE>; 2]>::deserialize(&mut buf).unwrap();
+        assert_eq!(array, [U16::new(1), U16::new(0x0203)]);
+
+        buf.set_offset(1);
+        assert!(<[U16<BE>; 2]>::deserialize(&mut buf).is_none());
+
+        let mut buf = Buffer::from(&[2, 0, 1, 2, 3, 9][..]);
+        let len = U8::deserialize(&mut buf).unwrap().get() as usize;
+        let counted = Counted::<U16<BE>>::deserialize_len(&mut buf, len).unwrap();
+        assert_eq!(counted.get(), [U16::new(1), U16::new(0x0203)]);
+        assert_eq!(U8::deserialize(&mut buf).unwrap().get(), 9);
+
+        buf.set_offset(1);
+        let field = <(U8, Counted<U8>)>::deserialize(&mut buf.take(4).unwrap()).unwrap();
+        assert_eq!(
+            field,
+            (
+                U8::new(0),
+                Counted::new(vec![U8::new(1), U8::new(2), U8::new(3)])
+            )
+        );
+        assert_eq!(U8::deserialize(&mut buf).unwrap().get(), 9);
+
+        let mut region = Buffer::from(&[0, 1, 2][..]);
+        assert!(Counted::<U16<BE>>::deserialize(&mut region).is_none());
+
+        let mut buf = Buffer::new(3);
+        (U8::new(2), Counted::<U8>::new(vec![U8::new(7), U8::new(8)])).serialize(&mut buf);
+        assert_eq!(&buf[..], &[2, 7, 8]);
+    }
+
+    ///
+    /// Tests that prefixed byte buffers round trip, that skipping lands on the same offset as
+    /// deserializing and that a prefix claiming more bytes than remain is rejected.
+    ///
+    #[test]
+    pub fn rem_buf() {
+        use crate::{Binary, Buffer, RemBuf, LE, U16, W32};
+
+        let mut buf = Buffer::new(6);
+        RemBuf::<U16<LE>>::new(vec![1, 2, 3]).serialize(&mut buf);
+        RemBuf::<W32>::new(vec![]).serialize(&mut buf);
+        assert_eq!(&buf[..], &[3, 0, 1, 2, 3, 0]);
+
+        buf.set_offset(0);
+        let val = RemBuf::<U16<LE>>::deserialize(&mut buf).unwrap();
+        assert_eq!(val.get(), &[1, 2, 3]);
+        assert!(RemBuf::<W32>::deserialize(&mut buf).unwrap().is_empty());
+
+        buf.set_offset(0);
+        RemBuf::<U16<LE>>::skip(&mut buf).unwrap();
+        assert_eq!(buf.offset(), 5);
+        RemBuf::<W32>::skip(&mut buf).unwrap();
+        assert_eq!(buf.remaining(), 0);
 
-        let slice = &buf.get_ref()[start..end];
+        buf.set_offset(0);
+        buf.resize(3);
+        assert!(RemBuf::<U16<LE>>::deserialize(&mut buf).is_none());
 
-        Some(Self::new(slice))
+        buf.set_offset(0);
+        assert!(RemBuf::<U16<LE>>::skip(&mut buf).is_none());
     }
 }
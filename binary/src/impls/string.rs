@@ -1,24 +1,240 @@
-use crate::{generate, Binary, Buffer, Prefix};
+use crate::{generate, impl_binary_ctx, Binary, Buffer, Prefix};
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
 
 generate!(CString, <P: Prefix>, String);
+generate!(MString, <P: Prefix>, String);
+generate!(LossyString, <P: Prefix>, String);
+generate!(RawString, <P: Prefix>, Vec<u8>);
+generate!(Latin1String, <P: Prefix>, String);
+generate!(NulString, <>, String);
+
+/// Reads a length prefix followed by that many bytes and returns a reference to the bytes.
+/// Returns None if the buffer does not hold the amount of bytes the prefix claims. Skipping a
+/// string only validates the prefix, not the encoding of the bytes.
+fn read_prefixed<'b, P: Prefix>(buf: &'b mut Buffer) -> Option<&'b [u8]> {
+    let len = P::decode(buf)?;
+    if buf.remaining() < len {
+        return None;
+    }
+
+    Some(buf.get(len, true))
+}
+
+/// Returns the payload bits of the continuation byte at the index of a Modified UTF-8 sequence.
+fn continuation(bytes: &[u8], index: usize) -> Option<u16> {
+    match bytes.get(index) {
+        Some(byte) if byte & 0xc0 == 0x80 => Some((byte & 0x3f) as u16),
+        _ => None,
+    }
+}
 
 impl<P: Prefix> Binary for CString<P> {
     fn serialize(&self, buf: &mut Buffer) {
         P::encode(self.len(), buf);
-        buf.write(&self.as_bytes());
+        buf.write(self.as_bytes());
     }
 
+    /// Returns None if the bytes are not valid UTF-8.
     fn deserialize(buf: &mut Buffer) -> Option<Self> {
-        let len = P::decode(buf)?;
+        buf.trace("CString", |buf| {
+            let bytes = read_prefixed::<P>(buf)?;
+            let str = core::str::from_utf8(bytes).ok()?;
+
+            Some(Self::new(str.to_owned()))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        read_prefixed::<P>(buf).map(|_| ())
+    }
+}
 
-        let mut vec = vec![0u8; len];
-        buf.read(&mut vec);
+impl_binary_ctx!(CString<P: Prefix>);
+
+/// MString is serialized using Modified UTF-8 as used by Java's DataOutput. It differs from UTF-8
+/// in two ways: NUL is written as the two bytes `C0 80`, and characters outside of the Basic
+/// Multilingual Plane are written as a surrogate pair of three bytes each. The prefix holds the
+/// number of encoded bytes.
+impl<P: Prefix> Binary for MString<P> {
+    fn serialize(&self, buf: &mut Buffer) {
+        let mut vec = Vec::with_capacity(self.len());
+
+        for unit in self.encode_utf16() {
+            match unit {
+                0x0001..=0x007f => vec.push(unit as u8),
+                0x0000 | 0x0080..=0x07ff => {
+                    vec.push(0xc0 | (unit >> 6) as u8);
+                    vec.push(0x80 | (unit & 0x3f) as u8);
+                }
+                _ => {
+                    vec.push(0xe0 | (unit >> 12) as u8);
+                    vec.push(0x80 | ((unit >> 6) & 0x3f) as u8);
+                    vec.push(0x80 | (unit & 0x3f) as u8);
+                }
+            }
+        }
+
+        P::encode(vec.len(), buf);
+        buf.write(&vec);
+    }
+
+    /// Returns None if a sequence is malformed or truncated or if a surrogate is left unpaired.
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("MString", |buf| {
+            let bytes = read_prefixed::<P>(buf)?;
+            let mut units = Vec::with_capacity(bytes.len());
+
+            let mut i = 0;
+            while i < bytes.len() {
+                let lead = bytes[i] as u16;
+                let (unit, len) = match lead {
+                    0x00..=0x7f => (lead, 1),
+                    0xc0..=0xdf => ((lead & 0x1f) << 6 | continuation(bytes, i + 1)?, 2),
+                    0xe0..=0xef => {
+                        let mid = continuation(bytes, i + 1)?;
+                        let low = continuation(bytes, i + 2)?;
+                        ((lead & 0x0f) << 12 | mid << 6 | low, 3)
+                    }
+                    _ => return None,
+                };
+
+                units.push(unit);
+                i += len;
+            }
+
+            let str = String::from_utf16(&units).ok()?;
+            Some(Self::new(str))
+        })
+    }
 
-        let str = String::from_utf8(vec).unwrap();
-        Some(Self::new(str))
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        read_prefixed::<P>(buf).map(|_| ())
     }
 }
 
+impl_binary_ctx!(MString<P: Prefix>);
+
+/// LossyString is serialized the same way as [`CString`] but replaces invalid UTF-8 sequences
+/// with U+FFFD when deserializing instead of failing. Use [`RawString`] if the original bytes
+/// have to be written back unchanged.
+impl<P: Prefix> Binary for LossyString<P> {
+    fn serialize(&self, buf: &mut Buffer) {
+        P::encode(self.len(), buf);
+        buf.write(self.as_bytes());
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("LossyString", |buf| {
+            let bytes = read_prefixed::<P>(buf)?;
+            let str = String::from_utf8_lossy(bytes);
+
+            Some(Self::new(str.into_owned()))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        read_prefixed::<P>(buf).map(|_| ())
+    }
+}
+
+impl_binary_ctx!(LossyString<P: Prefix>);
+
+/// RawString is serialized the same way as [`CString`] but keeps the bytes as they are, so
+/// strings holding invalid UTF-8 can be passed through without being altered.
+impl<P: Prefix> Binary for RawString<P> {
+    fn serialize(&self, buf: &mut Buffer) {
+        P::encode(self.len(), buf);
+        buf.write(self);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("RawString", |buf| {
+            let bytes = read_prefixed::<P>(buf)?;
+            Some(Self::new(bytes.to_vec()))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        read_prefixed::<P>(buf).map(|_| ())
+    }
+}
+
+impl_binary_ctx!(RawString<P: Prefix>);
+
+impl<P: Prefix> RawString<P> {
+    /// Returns the string with invalid UTF-8 sequences replaced by U+FFFD.
+    pub fn to_string_lossy(&self) -> alloc::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(self)
+    }
+}
+
+/// Latin1String is serialized using ISO-8859-1, one byte per character. Characters that cannot
+/// be represented in Latin-1 are written as `?`.
+impl<P: Prefix> Binary for Latin1String<P> {
+    fn serialize(&self, buf: &mut Buffer) {
+        let vec: Vec<u8> = self
+            .chars()
+            .map(|c| u8::try_from(c).unwrap_or(b'?'))
+            .collect();
+
+        P::encode(vec.len(), buf);
+        buf.write(&vec);
+    }
+
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("Latin1String", |buf| {
+            let bytes = read_prefixed::<P>(buf)?;
+            let str = bytes.iter().map(|&b| b as char).collect();
+
+            Some(Self::new(str))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        read_prefixed::<P>(buf).map(|_| ())
+    }
+}
+
+impl_binary_ctx!(Latin1String<P: Prefix>);
+
+/// NulString is serialized as UTF-8 followed by a NUL byte instead of a length prefix. Anything
+/// after an interior NUL is not written, as it could not be read back.
+impl Binary for NulString {
+    fn serialize(&self, buf: &mut Buffer) {
+        let bytes = self.as_bytes();
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+
+        buf.write(&bytes[..len]);
+        buf.write(&[0]);
+    }
+
+    /// Returns None if no NUL byte is found or if the bytes are not valid UTF-8.
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("NulString", |buf| {
+            let len = buf
+                .get(buf.remaining(), false)
+                .iter()
+                .position(|&b| b == 0)?;
+
+            let str = core::str::from_utf8(buf.get(len, true)).ok()?.to_owned();
+            buf.advance(1);
+
+            Some(Self::new(str))
+        })
+    }
+
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        let len = buf
+            .get(buf.remaining(), false)
+            .iter()
+            .position(|&b| b == 0)?;
+
+        buf.advance(len + 1).then_some(())
+    }
+}
+
+impl_binary_ctx!(NulString);
+
 mod tests {
     ///
     /// Tests the serialization and deserialization of string to the buffer
@@ -27,10 +243,59 @@ mod tests {
     pub fn string() {
         use crate::{Binary, Buffer, CString, W32};
 
-        let mut buffer = Buffer::new(0);
+        let mut buffer = Buffer::new(16);
         CString::<W32>::new("Hello World!".to_string()).serialize(&mut buffer);
+        buffer.set_offset(0);
 
         let str = CString::<W32>::deserialize(&mut buffer).unwrap();
         assert_eq!(str.get(), "Hello World!");
+
+        let mut buffer = Buffer::from(&[2, 0xc3, 0x28][..]);
+        assert!(CString::<W32>::deserialize(&mut buffer).is_none());
+
+        let mut buffer = Buffer::from(&[5, b'a'][..]);
+        assert!(CString::<W32>::deserialize(&mut buffer).is_none());
+    }
+
+    ///
+    /// Tests the encodings of the string variants against known bytes and reads them back.
+    ///
+    #[test]
+    pub fn variants() {
+        use crate::{Binary, Buffer, Latin1String, LossyString, MString, NulString, RawString};
+        use crate::{BE, U16, W32};
+
+        fn round_trip<B: Binary>(val: B, bytes: &[u8]) {
+            let mut buf = Buffer::new(bytes.len());
+            val.serialize(&mut buf);
+            assert_eq!(&buf[..], bytes);
+
+            buf.set_offset(0);
+            let read = B::deserialize(&mut buf).unwrap();
+            assert_eq!(format!("{read:?}"), format!("{val:?}"));
+        }
+
+        round_trip(
+            MString::<U16<BE>>::new("a\0é😀".into()),
+            &[
+                0, 11, b'a', 0xc0, 0x80, 0xc3, 0xa9, 0xed, 0xa0, 0xbd, 0xed, 0xb8, 0x80,
+            ],
+        );
+        round_trip(RawString::<W32>::new(vec![0xff, b'a']), &[2, 0xff, b'a']);
+        round_trip(
+            Latin1String::<W32>::new("café".into()),
+            &[4, b'c', b'a', b'f', 0xe9],
+        );
+        round_trip(NulString::new("MCPE".into()), b"MCPE\0");
+
+        let mut buf = Buffer::from(&[2, 0xff, b'a'][..]);
+        let str = LossyString::<W32>::deserialize(&mut buf).unwrap();
+        assert_eq!(str.get(), "\u{fffd}a");
+
+        let mut buf = Buffer::from(&[0, 3, 0xed, 0xa0, 0xbd][..]);
+        assert!(MString::<U16<BE>>::deserialize(&mut buf).is_none());
+
+        let mut buf = Buffer::from(&b"MCPE"[..]);
+        assert!(NulString::deserialize(&mut buf).is_none());
     }
 }
@@ -1,4 +1,9 @@
-use std::fmt::Debug;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 pub mod order;
 pub use order::*;
@@ -6,15 +11,351 @@ pub use order::*;
 pub mod prefix;
 pub use prefix::*;
 
+pub mod presence;
+pub use presence::*;
+
 pub mod impls;
 pub use impls::*;
 
 pub mod buffer;
 pub use buffer::*;
 
+pub mod bits;
+pub use bits::*;
+
+#[cfg(feature = "std")]
+pub mod pool;
+#[cfg(feature = "std")]
+pub use pool::*;
+
+#[cfg(feature = "trace")]
+pub mod trace;
+#[cfg(feature = "trace")]
+pub use trace::*;
+
+pub mod checksum;
+pub use checksum::*;
+
+#[cfg(feature = "bytes")]
+pub mod buf;
+#[cfg(feature = "bytes")]
+pub use buf::*;
+
+#[cfg(feature = "codec")]
+pub mod codec;
+#[cfg(feature = "codec")]
+pub use codec::*;
+
+/// Generators for property based testing. Every generated value is valid by construction, so it
+/// serializes to bytes that deserialize back into the same value: lengths stay within the limit
+/// of their prefix and values of odd widths, such as `U24`, stay within their bits.
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+pub use ::arbitrary as __arbitrary;
+
+/// Implements [`arbitrary::Arbitrary`] for a type generated by one of the macros of this crate if
+/// the `arbitrary` feature is enabled. The closure builds the value from the unstructured data.
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_arbitrary {
+    ($name:ident, |$u:ident| $body:expr) => {
+        impl<'a> $crate::__arbitrary::Arbitrary<'a> for $name {
+            fn arbitrary(
+                $u: &mut $crate::__arbitrary::Unstructured<'a>,
+            ) -> $crate::__arbitrary::Result<Self> {
+                $body
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "arbitrary"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_arbitrary {
+    ($name:ident, |$u:ident| $body:expr) => {};
+}
+
 /// Binary represents a trait that is implemented for all the objects that can be serialized
 /// and deserialized over the network.
 pub trait Binary: Sized + Debug {
     fn serialize(&self, buf: &mut Buffer);
     fn deserialize(buf: &mut Buffer) -> Option<Self>;
+
+    /// Serializes every object of the slice one after another. Numeric types override this to
+    /// write the whole slice at once.
+    fn serialize_slice(vals: &[Self], buf: &mut Buffer) {
+        for val in vals {
+            val.serialize(buf);
+        }
+    }
+
+    /// Deserializes len objects one after another. Numeric types override this to read the whole
+    /// run at once.
+    fn deserialize_vec(buf: &mut Buffer, len: usize) -> Option<Vec<Self>> {
+        let mut vec = Vec::with_capacity(len.min(buf.remaining()));
+
+        for _ in 0..len {
+            vec.push(Self::deserialize(buf)?);
+        }
+
+        Some(vec)
+    }
+
+    /// Advances the buffer past an object without materialising it. The framing of the object,
+    /// such as varint termination and length prefixes, is validated but its contents may not be.
+    /// Types whose deserialization allocates override this so that skipping allocates nothing.
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        Self::deserialize(buf).map(|_| ())
+    }
+
+    /// Advances the buffer past len objects. Fixed size types override this to advance the
+    /// buffer at once.
+    fn skip_many(buf: &mut Buffer, len: usize) -> Option<()> {
+        for _ in 0..len {
+            Self::skip(buf)?;
+        }
+
+        Some(())
+    }
+}
+
+/// EndianBinary represents a trait that is implemented for all the objects that are serialized
+/// using a byte order only known at runtime, see [`Endian`]. It is implemented for the primitive
+/// numeric types, for the wrappers of the [`endian`] module, which cover the odd widths such as
+/// 24-bit integers and half floats, and for every [`Binary`] object, which ignores the byte order.
+pub trait EndianBinary: Sized + Debug {
+    fn serialize_endian(&self, endian: Endian, buf: &mut Buffer);
+    fn deserialize_endian(endian: Endian, buf: &mut Buffer) -> Option<Self>;
+}
+
+impl<T: Binary> EndianBinary for T {
+    fn serialize_endian(&self, _: Endian, buf: &mut Buffer) {
+        Binary::serialize(self, buf)
+    }
+
+    fn deserialize_endian(_: Endian, buf: &mut Buffer) -> Option<Self> {
+        Binary::deserialize(buf)
+    }
+}
+
+/// BinaryCtx represents a trait that is implemented for all the objects whose layout depends on
+/// state negotiated outside of the buffer, such as the protocol version of a session. The
+/// [`Binary`] objects of this crate implement it for any context by ignoring the context, see
+/// [`impl_binary_ctx`], and the containers forward the context to their elements, so the wrappers
+/// can be used as fields of context-aware types, and hold them, without any adapters.
+pub trait BinaryCtx<C>: Sized + Debug {
+    fn serialize_ctx(&self, ctx: &C, buf: &mut Buffer);
+    fn deserialize_ctx(ctx: &C, buf: &mut Buffer) -> Option<Self>;
+
+    fn skip_ctx(ctx: &C, buf: &mut Buffer) -> Option<()> {
+        Self::deserialize_ctx(ctx, buf).map(|_| ())
+    }
+
+    /// Serializes every object of the slice one after another. Types that ignore the context
+    /// override this with [`Binary::serialize_slice`].
+    fn serialize_slice_ctx(vals: &[Self], ctx: &C, buf: &mut Buffer) {
+        for val in vals {
+            val.serialize_ctx(ctx, buf);
+        }
+    }
+
+    /// Deserializes len objects one after another. Types that ignore the context override this
+    /// with [`Binary::deserialize_vec`].
+    fn deserialize_vec_ctx(ctx: &C, buf: &mut Buffer, len: usize) -> Option<Vec<Self>> {
+        let mut vec = Vec::with_capacity(len.min(buf.remaining()));
+
+        for _ in 0..len {
+            vec.push(Self::deserialize_ctx(ctx, buf)?);
+        }
+
+        Some(vec)
+    }
+
+    /// Advances the buffer past len objects. Types that ignore the context override this with
+    /// [`Binary::skip_many`].
+    fn skip_many_ctx(ctx: &C, buf: &mut Buffer, len: usize) -> Option<()> {
+        for _ in 0..len {
+            Self::skip_ctx(ctx, buf)?;
+        }
+
+        Some(())
+    }
+}
+
+#[doc(hidden)]
+pub use alloc::vec::Vec as __Vec;
+
+/// Implements [`BinaryCtx`] for any context by forwarding to the [`Binary`] implementation of the
+/// type, which lets a type that ignores the context be held by context-aware containers.
+///
+/// ```
+/// #[derive(Debug)]
+/// struct Ping(binary::U8);
+///
+/// impl binary::Binary for Ping {
+///     fn serialize(&self, buf: &mut binary::Buffer) {
+///         self.0.serialize(buf);
+///     }
+///
+///     fn deserialize(buf: &mut binary::Buffer) -> Option<Self> {
+///         binary::U8::deserialize(buf).map(Self)
+///     }
+/// }
+///
+/// binary::impl_binary_ctx!(Ping);
+///
+/// let mut buf = binary::Buffer::new(1);
+/// binary::BinaryCtx::serialize_ctx(&Ping(binary::U8::new(7)), &7u32, &mut buf);
+/// assert_eq!(&buf[..], &[7]);
+/// ```
+#[macro_export]
+macro_rules! impl_binary_ctx {
+    ($name:ident $(<$($gen:ident: $bound:path),+>)?) => {
+        impl<Ctx $($(, $gen: $bound)+)?> $crate::BinaryCtx<Ctx> for $name$(<$($gen),+>)? {
+            fn serialize_ctx(&self, _: &Ctx, buf: &mut $crate::Buffer) {
+                $crate::Binary::serialize(self, buf)
+            }
+
+            fn deserialize_ctx(_: &Ctx, buf: &mut $crate::Buffer) -> Option<Self> {
+                <Self as $crate::Binary>::deserialize(buf)
+            }
+
+            fn skip_ctx(_: &Ctx, buf: &mut $crate::Buffer) -> Option<()> {
+                <Self as $crate::Binary>::skip(buf)
+            }
+
+            fn serialize_slice_ctx(vals: &[Self], _: &Ctx, buf: &mut $crate::Buffer) {
+                <Self as $crate::Binary>::serialize_slice(vals, buf)
+            }
+
+            fn deserialize_vec_ctx(
+                _: &Ctx,
+                buf: &mut $crate::Buffer,
+                len: usize,
+            ) -> Option<$crate::__Vec<Self>> {
+                <Self as $crate::Binary>::deserialize_vec(buf, len)
+            }
+
+            fn skip_many_ctx(_: &Ctx, buf: &mut $crate::Buffer, len: usize) -> Option<()> {
+                <Self as $crate::Binary>::skip_many(buf, len)
+            }
+        }
+    };
+}
+
+mod tests {
+    ///
+    /// Checks that a context-aware type can change its layout depending on the context, while
+    /// the wrappers it is built from ignore the context.
+    ///
+    #[test]
+    pub fn context() {
+        use crate::{BinaryCtx, Bool, Buffer, V32, W32};
+
+        #[derive(Debug, PartialEq)]
+        struct Packet {
+            id: V32,
+            hashed: Option<Bool>,
+        }
+
+        impl BinaryCtx<u32> for Packet {
+            fn serialize_ctx(&self, protocol: &u32, buf: &mut Buffer) {
+                self.id.serialize_ctx(protocol, buf);
+                if *protocol >= 594 {
+                    self.hashed.as_ref().unwrap().serialize_ctx(protocol, buf);
+                }
+            }
+
+            fn deserialize_ctx(protocol: &u32, buf: &mut Buffer) -> Option<Self> {
+                let id = V32::deserialize_ctx(protocol, buf)?;
+                let hashed = match *protocol >= 594 {
+                    true => Some(Bool::deserialize_ctx(protocol, buf)?),
+                    false => None,
+                };
+                Some(Self { id, hashed })
+            }
+        }
+
+        for (protocol, hashed, len) in [(589, None, 1), (594, Some(Bool::new(true)), 2)] {
+            let packet = Packet {
+                id: V32::new(-3),
+                hashed,
+            };
+            let mut buf = Buffer::new(8);
+            packet.serialize_ctx(&protocol, &mut buf);
+            assert_eq!(buf.offset(), len);
+
+            buf.set_offset(0);
+            assert_eq!(Packet::deserialize_ctx(&protocol, &mut buf), Some(packet));
+        }
+
+        let mut buf = Buffer::new(8);
+        W32::new(7).serialize_ctx(&(), &mut buf);
+        assert_eq!(buf.offset(), 1);
+    }
+
+    ///
+    /// Checks that the containers forward the context to context-aware elements, and that
+    /// skipping lands on the same offset as deserializing.
+    ///
+    #[test]
+    pub fn context_array() {
+        use crate::{Array, BinaryCtx, Bool, Buffer, Optional, U8, W32};
+
+        #[derive(Debug, PartialEq)]
+        struct Entry {
+            id: U8,
+            hidden: Option<Bool>,
+        }
+
+        impl BinaryCtx<u32> for Entry {
+            fn serialize_ctx(&self, protocol: &u32, buf: &mut Buffer) {
+                self.id.serialize_ctx(protocol, buf);
+                if *protocol >= 594 {
+                    self.hidden.as_ref().unwrap().serialize_ctx(protocol, buf);
+                }
+            }
+
+            fn deserialize_ctx(protocol: &u32, buf: &mut Buffer) -> Option<Self> {
+                let id = U8::deserialize_ctx(protocol, buf)?;
+                let hidden = match *protocol >= 594 {
+                    true => Some(Bool::deserialize_ctx(protocol, buf)?),
+                    false => None,
+                };
+                Some(Self { id, hidden })
+            }
+        }
+
+        type Entries = (Array<W32, Entry>, Optional<Bool, Entry>);
+
+        for (protocol, hidden, bytes) in [
+            (589, None, &[2, 1, 2, 1, 3][..]),
+            (594, Some(Bool::new(false)), &[2, 1, 0, 2, 0, 1, 3, 0][..]),
+        ] {
+            let entry = |id| Entry {
+                id: U8::new(id),
+                hidden,
+            };
+            let entries: Entries = (
+                Array::new(vec![entry(1), entry(2)]),
+                Optional::new(Some(entry(3))),
+            );
+
+            let mut buf = Buffer::new(bytes.len());
+            entries.serialize_ctx(&protocol, &mut buf);
+            assert_eq!(&buf[..], bytes);
+
+            buf.set_offset(0);
+            assert_eq!(Entries::deserialize_ctx(&protocol, &mut buf), Some(entries));
+
+            buf.set_offset(0);
+            Entries::skip_ctx(&protocol, &mut buf).unwrap();
+            assert_eq!(buf.remaining(), 0);
+        }
+    }
 }
@@ -1,9 +1,82 @@
-use crate::Buffer;
+use crate::{Buffer, EndianBinary};
+
+/// This macro generates the bulk read and write methods of [`ByteOrder`] for slices of numbers.
+/// The bytes are copied as is if the byte order matches the byte order of the target platform
+/// and are swapped in a tight loop otherwise.
+macro_rules! impl_slice {
+    ($($ty:ty, $read_method:ident, $write_method:ident);* $(;)?) => {
+        $(
+            /// Fills the slice with values read from the buffer. Returns None without reading
+            /// anything if the buffer does not have enough bytes left for the whole slice.
+            fn $read_method(buf: &mut Buffer, dst: &mut [$ty]) -> Option<()> {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                let len = dst.len() * SIZE;
+                if buf.remaining() < len {
+                    return None;
+                }
+
+                let src = buf.get(len, true);
+                if Self::ENDIAN == Endian::NATIVE {
+                    // SAFETY: Both regions are len bytes long and every bit pattern is a valid
+                    // value of the numeric type.
+                    unsafe {
+                        let dst = dst.as_mut_ptr() as *mut u8;
+                        core::ptr::copy_nonoverlapping(src.as_ptr(), dst, len)
+                    }
+                } else {
+                    for (val, bytes) in dst.iter_mut().zip(src.chunks_exact(SIZE)) {
+                        let bytes = bytes.try_into().unwrap();
+                        *val = match Self::ENDIAN {
+                            Endian::Little => <$ty>::from_le_bytes(bytes),
+                            Endian::Big => <$ty>::from_be_bytes(bytes),
+                        };
+                    }
+                }
+
+                Some(())
+            }
+
+            /// Writes every value of the slice into the buffer. If the buffer does not have
+            /// enough space for the whole slice then it writes as many values as it can.
+            fn $write_method(src: &[$ty], buf: &mut Buffer) {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                let n = src.len().min(buf.remaining() / SIZE);
+                let (start, len) = (buf.offset(), n * SIZE);
+
+                let dst = match buf.as_mut_slice() {
+                    Some(slice) => &mut slice[start..start + len],
+                    None => return,
+                };
+                if Self::ENDIAN == Endian::NATIVE {
+                    // SAFETY: Both regions are len bytes long.
+                    unsafe {
+                        let src = src.as_ptr() as *const u8;
+                        core::ptr::copy_nonoverlapping(src, dst.as_mut_ptr(), len)
+                    }
+                } else {
+                    for (val, bytes) in src.iter().zip(dst.chunks_exact_mut(SIZE)) {
+                        bytes.copy_from_slice(&match Self::ENDIAN {
+                            Endian::Little => val.to_le_bytes(),
+                            Endian::Big => val.to_be_bytes(),
+                        });
+                    }
+                }
+
+                buf.advance(len);
+            }
+        )*
+    };
+}
 
 /// ByteOrder represents a trait that is implemened by [`LE`] and [`BE`] i.e. LittleEndian
 /// and BigEndian respectively. They define how bytes are ordered while transmitting data
 /// over the network or storing locally.
 pub trait ByteOrder {
+    /// The runtime equivalent of this byte order.
+    const ENDIAN: Endian;
+
     fn read_u16(buf: &mut Buffer) -> Option<u16>;
     fn write_u16(val: u16, buf: &mut Buffer);
 
@@ -13,23 +86,51 @@ pub trait ByteOrder {
     fn read_u24(buf: &mut Buffer) -> Option<u32>;
     fn write_u24(val: u32, buf: &mut Buffer);
 
+    fn read_i24(buf: &mut Buffer) -> Option<i32>;
+    fn write_i24(val: i32, buf: &mut Buffer);
+
     fn read_u32(buf: &mut Buffer) -> Option<u32>;
     fn write_u32(val: u32, buf: &mut Buffer);
 
     fn read_i32(buf: &mut Buffer) -> Option<i32>;
     fn write_i32(val: i32, buf: &mut Buffer);
 
+    fn read_u48(buf: &mut Buffer) -> Option<u64>;
+    fn write_u48(val: u64, buf: &mut Buffer);
+
     fn read_u64(buf: &mut Buffer) -> Option<u64>;
     fn write_u64(val: u64, buf: &mut Buffer);
 
     fn read_i64(buf: &mut Buffer) -> Option<i64>;
     fn write_i64(val: i64, buf: &mut Buffer);
 
+    fn read_u128(buf: &mut Buffer) -> Option<u128>;
+    fn write_u128(val: u128, buf: &mut Buffer);
+
+    fn read_i128(buf: &mut Buffer) -> Option<i128>;
+    fn write_i128(val: i128, buf: &mut Buffer);
+
+    /// Reads a half precision float and widens it to single precision.
+    fn read_f16(buf: &mut Buffer) -> Option<f32>;
+    /// Writes the value as a half precision float rounding it to the nearest representable value.
+    fn write_f16(val: f32, buf: &mut Buffer);
+
     fn read_f32(buf: &mut Buffer) -> Option<f32>;
     fn write_f32(val: f32, buf: &mut Buffer);
 
     fn read_f64(buf: &mut Buffer) -> Option<f64>;
     fn write_f64(val: f64, buf: &mut Buffer);
+
+    impl_slice!(
+        u16, read_u16_slice, write_u16_slice;
+        i16, read_i16_slice, write_i16_slice;
+        u32, read_u32_slice, write_u32_slice;
+        i32, read_i32_slice, write_i32_slice;
+        u64, read_u64_slice, write_u64_slice;
+        i64, read_i64_slice, write_i64_slice;
+        f32, read_f32_slice, write_f32_slice;
+        f64, read_f64_slice, write_f64_slice;
+    );
 }
 
 /// LE is the little endian byte ordering in which the least significant byte is stored at the smallest
@@ -43,6 +144,8 @@ pub struct LE;
 pub struct BE;
 
 impl ByteOrder for LE {
+    const ENDIAN: Endian = Endian::Little;
+
     fn read_u16(buf: &mut Buffer) -> Option<u16> {
         let mut bytes = [0_u8; 2];
         if buf.read(&mut bytes) == 2 {
@@ -82,6 +185,20 @@ impl ByteOrder for LE {
         buf.write(&bytes);
     }
 
+    fn read_i24(buf: &mut Buffer) -> Option<i32> {
+        let mut bytes = [0_u8; 3];
+        if buf.read(&mut bytes) == 3 {
+            let val = (bytes[0] as u32) | (bytes[1] as u32) << 8 | (bytes[2] as u32) << 16;
+            return Some(((val << 8) as i32) >> 8);
+        }
+        None
+    }
+
+    fn write_i24(val: i32, buf: &mut Buffer) {
+        let bytes = [val as u8, (val >> 8) as u8, (val >> 16) as u8];
+        buf.write(&bytes);
+    }
+
     fn read_u32(buf: &mut Buffer) -> Option<u32> {
         let mut bytes = [0_u8; 4];
         if buf.read(&mut bytes) == 4 {
@@ -108,6 +225,19 @@ impl ByteOrder for LE {
         buf.write(&bytes);
     }
 
+    fn read_u48(buf: &mut Buffer) -> Option<u64> {
+        let mut bytes = [0_u8; 8];
+        if buf.read(&mut bytes[..6]) == 6 {
+            return Some(u64::from_le_bytes(bytes));
+        }
+        None
+    }
+
+    fn write_u48(val: u64, buf: &mut Buffer) {
+        let bytes = val.to_le_bytes();
+        buf.write(&bytes[..6]);
+    }
+
     fn read_u64(buf: &mut Buffer) -> Option<u64> {
         let mut bytes = [0_u8; 8];
         if buf.read(&mut bytes) == 8 {
@@ -134,6 +264,45 @@ impl ByteOrder for LE {
         buf.write(&bytes);
     }
 
+    fn read_u128(buf: &mut Buffer) -> Option<u128> {
+        let mut bytes = [0_u8; 16];
+        if buf.read(&mut bytes) == 16 {
+            return Some(u128::from_le_bytes(bytes));
+        }
+        None
+    }
+
+    fn write_u128(val: u128, buf: &mut Buffer) {
+        let bytes = val.to_le_bytes();
+        buf.write(&bytes);
+    }
+
+    fn read_i128(buf: &mut Buffer) -> Option<i128> {
+        let mut bytes = [0_u8; 16];
+        if buf.read(&mut bytes) == 16 {
+            return Some(i128::from_le_bytes(bytes));
+        }
+        None
+    }
+
+    fn write_i128(val: i128, buf: &mut Buffer) {
+        let bytes = val.to_le_bytes();
+        buf.write(&bytes);
+    }
+
+    fn read_f16(buf: &mut Buffer) -> Option<f32> {
+        let mut bytes = [0_u8; 2];
+        if buf.read(&mut bytes) == 2 {
+            return Some(f16_to_f32(u16::from_le_bytes(bytes)));
+        }
+        None
+    }
+
+    fn write_f16(val: f32, buf: &mut Buffer) {
+        let bytes = f32_to_f16(val).to_le_bytes();
+        buf.write(&bytes);
+    }
+
     fn read_f32(buf: &mut Buffer) -> Option<f32> {
         let mut bytes = [0_u8; 4];
         if buf.read(&mut bytes) == 4 {
@@ -162,6 +331,8 @@ impl ByteOrder for LE {
 }
 
 impl ByteOrder for BE {
+    const ENDIAN: Endian = Endian::Big;
+
     fn read_u16(buf: &mut Buffer) -> Option<u16> {
         let mut bytes = [0_u8; 2];
         if buf.read(&mut bytes) == 2 {
@@ -201,6 +372,20 @@ impl ByteOrder for BE {
         buf.write(&bytes);
     }
 
+    fn read_i24(buf: &mut Buffer) -> Option<i32> {
+        let mut bytes = [0_u8; 3];
+        if buf.read(&mut bytes) == 3 {
+            let val = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+            return Some(((val << 8) as i32) >> 8);
+        }
+        None
+    }
+
+    fn write_i24(val: i32, buf: &mut Buffer) {
+        let bytes = [(val >> 16) as u8, (val >> 8) as u8, val as u8];
+        buf.write(&bytes);
+    }
+
     fn read_u32(buf: &mut Buffer) -> Option<u32> {
         let mut bytes = [0_u8; 4];
         if buf.read(&mut bytes) == 4 {
@@ -227,6 +412,19 @@ impl ByteOrder for BE {
         buf.write(&bytes);
     }
 
+    fn read_u48(buf: &mut Buffer) -> Option<u64> {
+        let mut bytes = [0_u8; 8];
+        if buf.read(&mut bytes[2..]) == 6 {
+            return Some(u64::from_be_bytes(bytes));
+        }
+        None
+    }
+
+    fn write_u48(val: u64, buf: &mut Buffer) {
+        let bytes = val.to_be_bytes();
+        buf.write(&bytes[2..]);
+    }
+
     fn read_u64(buf: &mut Buffer) -> Option<u64> {
         let mut bytes = [0_u8; 8];
         if buf.read(&mut bytes) == 8 {
@@ -253,6 +451,45 @@ impl ByteOrder for BE {
         buf.write(&bytes);
     }
 
+    fn read_u128(buf: &mut Buffer) -> Option<u128> {
+        let mut bytes = [0_u8; 16];
+        if buf.read(&mut bytes) == 16 {
+            return Some(u128::from_be_bytes(bytes));
+        }
+        None
+    }
+
+    fn write_u128(val: u128, buf: &mut Buffer) {
+        let bytes = val.to_be_bytes();
+        buf.write(&bytes);
+    }
+
+    fn read_i128(buf: &mut Buffer) -> Option<i128> {
+        let mut bytes = [0_u8; 16];
+        if buf.read(&mut bytes) == 16 {
+            return Some(i128::from_be_bytes(bytes));
+        }
+        None
+    }
+
+    fn write_i128(val: i128, buf: &mut Buffer) {
+        let bytes = val.to_be_bytes();
+        buf.write(&bytes);
+    }
+
+    fn read_f16(buf: &mut Buffer) -> Option<f32> {
+        let mut bytes = [0_u8; 2];
+        if buf.read(&mut bytes) == 2 {
+            return Some(f16_to_f32(u16::from_be_bytes(bytes)));
+        }
+        None
+    }
+
+    fn write_f16(val: f32, buf: &mut Buffer) {
+        let bytes = f32_to_f16(val).to_be_bytes();
+        buf.write(&bytes);
+    }
+
     fn read_f32(buf: &mut Buffer) -> Option<f32> {
         let mut bytes = [0_u8; 4];
         if buf.read(&mut bytes) == 4 {
@@ -279,3 +516,257 @@ impl ByteOrder for BE {
         buf.write(&bytes);
     }
 }
+
+/// Endian is a byte order that is only known at runtime, such as the byte order of a file which
+/// is detected from its header. It provides the same functions as [`ByteOrder`] which dispatch to
+/// [`LE`] or [`BE`]. Static byte orders should be preferred whenever they are known at compile
+/// time as they do not need to branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the target platform.
+    pub const NATIVE: Endian = if cfg!(target_endian = "little") {
+        Endian::Little
+    } else {
+        Endian::Big
+    };
+}
+
+macro_rules! impl_endian {
+    ($($read_method:ident, $write_method:ident, $ty:ty);* $(;)?) => {
+        impl Endian {
+            $(
+                pub fn $read_method(self, buf: &mut Buffer) -> Option<$ty> {
+                    match self {
+                        Endian::Little => LE::$read_method(buf),
+                        Endian::Big => BE::$read_method(buf),
+                    }
+                }
+
+                pub fn $write_method(self, val: $ty, buf: &mut Buffer) {
+                    match self {
+                        Endian::Little => LE::$write_method(val, buf),
+                        Endian::Big => BE::$write_method(val, buf),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+impl_endian!(
+    read_u16, write_u16, u16;
+    read_i16, write_i16, i16;
+    read_u24, write_u24, u32;
+    read_i24, write_i24, i32;
+    read_u32, write_u32, u32;
+    read_i32, write_i32, i32;
+    read_u48, write_u48, u64;
+    read_u64, write_u64, u64;
+    read_i64, write_i64, i64;
+    read_u128, write_u128, u128;
+    read_i128, write_i128, i128;
+    read_f16, write_f16, f32;
+    read_f32, write_f32, f32;
+    read_f64, write_f64, f64;
+);
+
+macro_rules! impl_endian_binary {
+    ($($ty:ty, $read_method:ident, $write_method:ident);* $(;)?) => {
+        $(
+            impl EndianBinary for $ty {
+                fn serialize_endian(&self, endian: Endian, buf: &mut Buffer) {
+                    endian.$write_method(*self, buf)
+                }
+
+                fn deserialize_endian(endian: Endian, buf: &mut Buffer) -> Option<Self> {
+                    endian.$read_method(buf)
+                }
+            }
+        )*
+    };
+}
+
+impl_endian_binary!(
+    u16, read_u16, write_u16;
+    i16, read_i16, write_i16;
+    u32, read_u32, write_u32;
+    i32, read_i32, write_i32;
+    u64, read_u64, write_u64;
+    i64, read_i64, write_i64;
+    u128, read_u128, write_u128;
+    i128, read_i128, write_i128;
+    f32, read_f32, write_f32;
+    f64, read_f64, write_f64;
+);
+
+/// Converts the bits of a half precision float into a single precision float.
+fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exp = ((half >> 10) & 0x1f) as u32;
+    let man = (half & 0x3ff) as u32;
+
+    let bits = match exp {
+        0 if man == 0 => sign,
+        0 => {
+            // Subnormal halves are normal in single precision, so the mantissa is shifted until
+            // its implicit leading bit is set.
+            let shift = man.leading_zeros() - 21;
+            sign | ((113 - shift) << 23) | (((man << shift) & 0x3ff) << 13)
+        }
+        0x1f => sign | 0x7f80_0000 | (man << 13),
+        _ => sign | ((exp + 112) << 23) | (man << 13),
+    };
+
+    f32::from_bits(bits)
+}
+
+/// Converts a single precision float into the bits of a half precision float rounding to the
+/// nearest even value. Values too large to be represented become infinity.
+fn f32_to_f16(val: f32) -> u16 {
+    let bits = val.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let man = bits & 0x7f_ffff;
+
+    if exp == 0xff {
+        let nan = if man != 0 {
+            0x200 | (man >> 13) as u16
+        } else {
+            0
+        };
+        return sign | 0x7c00 | nan;
+    }
+
+    let exp = exp - 112;
+    if exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    let (half, rem, shift) = if exp <= 0 {
+        if exp < -10 {
+            return sign;
+        }
+
+        let man = man | 0x80_0000;
+        let shift = (14 - exp) as u32;
+        (man >> shift, man & ((1 << shift) - 1), shift)
+    } else {
+        (((exp as u32) << 10) | (man >> 13), man & 0x1fff, 13)
+    };
+
+    // Rounding may carry into the exponent which correctly yields the next power of two or
+    // infinity.
+    let mid = 1 << (shift - 1);
+    let half = if rem > mid || (rem == mid && half & 1 == 1) {
+        half + 1
+    } else {
+        half
+    };
+
+    sign | half as u16
+}
+
+mod tests {
+    ///
+    /// Tests the odd and wide integer widths against known bytes in both byte orders, including
+    /// the sign extension of 24-bit values.
+    ///
+    #[test]
+    pub fn widths() {
+        use crate::{Buffer, ByteOrder, BE, LE};
+
+        let mut buf = Buffer::from(&[0xfe, 0xff, 0xff, 0x00, 0x00, 0x80, 0xff, 0xff, 0x7f][..]);
+        assert_eq!(LE::read_i24(&mut buf), Some(-2));
+        assert_eq!(LE::read_i24(&mut buf), Some(-0x80_0000));
+        assert_eq!(LE::read_i24(&mut buf), Some(0x7f_ffff));
+        assert_eq!(LE::read_i24(&mut buf), None);
+
+        let mut buf = Buffer::from(&[0xff, 0xff, 0xfe, 0x80, 0x00, 0x00][..]);
+        assert_eq!(BE::read_i24(&mut buf), Some(-2));
+        assert_eq!(BE::read_i24(&mut buf), Some(-0x80_0000));
+
+        let mut buf = Buffer::new(6);
+        LE::write_i24(-2, &mut buf);
+        BE::write_i24(-2, &mut buf);
+        assert_eq!(&buf[..], &[0xfe, 0xff, 0xff, 0xff, 0xff, 0xfe]);
+
+        let mut buf = Buffer::new(12);
+        LE::write_u48(0x0102_0304_0506, &mut buf);
+        BE::write_u48(0xffff_ffff_ffff, &mut buf);
+        assert_eq!(&buf[..6], &[6, 5, 4, 3, 2, 1]);
+        assert_eq!(&buf[6..], &[0xff; 6]);
+
+        buf.set_offset(0);
+        assert_eq!(LE::read_u48(&mut buf), Some(0x0102_0304_0506));
+        assert_eq!(BE::read_u48(&mut buf), Some(0xffff_ffff_ffff));
+
+        let val = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128;
+        let mut buf = Buffer::new(64);
+        LE::write_u128(val, &mut buf);
+        BE::write_u128(val, &mut buf);
+        LE::write_i128(i128::MIN, &mut buf);
+        BE::write_i128(i128::MIN, &mut buf);
+        assert_eq!(buf[0], 0x10);
+        assert_eq!(buf[16], 0x01);
+        assert_eq!(buf[47], 0x80);
+        assert_eq!(buf[48], 0x80);
+
+        buf.set_offset(0);
+        assert_eq!(LE::read_u128(&mut buf), Some(val));
+        assert_eq!(BE::read_u128(&mut buf), Some(val));
+        assert_eq!(LE::read_i128(&mut buf), Some(i128::MIN));
+        assert_eq!(BE::read_i128(&mut buf), Some(i128::MIN));
+    }
+
+    ///
+    /// Tests the conversions between half and single precision floats for normal and subnormal
+    /// values, rounding ties to even, infinities and NaN.
+    ///
+    #[test]
+    pub fn half() {
+        use crate::{Buffer, ByteOrder, BE, LE};
+
+        fn write(val: f32) -> u16 {
+            let mut buf = Buffer::new(2);
+            LE::write_f16(val, &mut buf);
+            u16::from_le_bytes([buf[0], buf[1]])
+        }
+
+        fn read(half: u16) -> f32 {
+            let bytes = half.to_be_bytes();
+            BE::read_f16(&mut Buffer::from(&bytes)).unwrap()
+        }
+
+        for (val, half) in [
+            (1.0, 0x3c00),
+            (-2.0, 0xc000),
+            (-0.0, 0x8000),
+            (65504.0, 0x7bff),
+            (6.1035156e-5, 0x0400),
+            (6.097555e-5, 0x03ff),
+            (5.9604645e-8, 0x0001),
+            (f32::INFINITY, 0x7c00),
+            (f32::NEG_INFINITY, 0xfc00),
+        ] {
+            assert_eq!(write(val), half);
+            assert_eq!(read(half).to_bits(), val.to_bits());
+        }
+
+        // Values half way between two halves round to the one with an even mantissa.
+        assert_eq!(write(1.0 + 2f32.powi(-11)), 0x3c00);
+        assert_eq!(write(1.0 + 3.0 * 2f32.powi(-11)), 0x3c02);
+        assert_eq!(write(2f32.powi(-25)), 0x0000);
+        assert_eq!(write(3.0 * 2f32.powi(-25)), 0x0002);
+        assert_eq!(write(65520.0), 0x7c00);
+        assert_eq!(write(1e-10), 0x0000);
+
+        assert_eq!(write(f32::NAN) & 0x7c00, 0x7c00);
+        assert_ne!(write(f32::NAN) & 0x3ff, 0);
+        assert!(read(0x7e00).is_nan());
+    }
+}
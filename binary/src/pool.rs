@@ -0,0 +1,209 @@
+use crate::Buffer;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Pool represents a thread safe pool of buffers which can be reused for encoding and decoding
+/// packets without allocating. Buffers are grouped into size classes of powers of two between the
+/// minimum and maximum size of the pool, and a request is served by the smallest class that fits
+/// it. Buffers are returned to the pool once dropped unless the pool already retains as many
+/// bytes as its limit allows. Buffers are handed out from an [`Arc`] of the pool so that they can
+/// outlive the scope they were requested in, such as when they are moved into a spawned task.
+pub struct Pool {
+    min: usize,
+    classes: Vec<Mutex<Vec<Vec<u8>>>>,
+    limit: usize,
+    retained: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Stats represents a snapshot of the statistics of a [`Pool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of requests that were served by a retained buffer.
+    pub hits: u64,
+    /// The number of requests that needed a new buffer to be allocated.
+    pub misses: u64,
+    /// The number of bytes currently retained by the pool.
+    pub retained: usize,
+}
+
+impl Pool {
+    /// Creates and returns a new Pool with size classes from min to max bytes which retains at
+    /// most limit bytes. Both sizes are rounded up to the next power of two, or down to the largest
+    /// power of two if there is no larger one.
+    pub fn new(min: usize, max: usize, limit: usize) -> Self {
+        const LARGEST: usize = 1 << (usize::BITS - 1);
+
+        let min = min.max(1).checked_next_power_of_two().unwrap_or(LARGEST);
+        let max = max.max(min).checked_next_power_of_two().unwrap_or(LARGEST);
+
+        let n = (max.trailing_zeros() - min.trailing_zeros()) as usize + 1;
+
+        Self {
+            min,
+            classes: (0..n).map(|_| Mutex::new(Vec::new())).collect(),
+            limit,
+            retained: AtomicUsize::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a buffer of at least the specified capacity. The contents of the buffer are not
+    /// cleared if it is reused and requests above the maximum size of the pool are allocated
+    /// separately and never retained.
+    pub fn get(self: &Arc<Self>, cap: usize) -> Pooled {
+        let vec = match self.class(cap) {
+            Some(class) => match self.classes[class].lock().unwrap().pop() {
+                Some(vec) => {
+                    self.retained.fetch_sub(vec.len(), Ordering::Relaxed);
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    vec
+                }
+                None => {
+                    self.misses.fetch_add(1, Ordering::Relaxed);
+                    vec![0u8; self.min << class]
+                }
+            },
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                vec![0u8; cap]
+            }
+        };
+
+        Pooled {
+            pool: Arc::clone(self),
+            buf: Some(Buffer::from(vec)),
+        }
+    }
+
+    /// Returns the statistics of the pool.
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            retained: self.retained.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the index of the smallest size class that fits the capacity.
+    fn class(&self, cap: usize) -> Option<usize> {
+        let size = cap.max(self.min).checked_next_power_of_two()?;
+        let class = (size.trailing_zeros() - self.min.trailing_zeros()) as usize;
+
+        (class < self.classes.len()).then_some(class)
+    }
+
+    /// Returns the bytes to the pool if they belong to a size class and the limit allows it.
+    fn put(&self, vec: Vec<u8>) {
+        let class = match self.class(vec.len()) {
+            Some(class) if self.min << class == vec.len() => class,
+            _ => return,
+        };
+
+        let len = vec.len();
+        let reserved = self
+            .retained
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                (n + len <= self.limit).then_some(n + len)
+            });
+
+        if reserved.is_ok() {
+            self.classes[class].lock().unwrap().push(vec);
+        }
+    }
+}
+
+/// Pooled represents a buffer borrowed from a [`Pool`]. It dereferences to the buffer and
+/// returns it to the pool once dropped. It keeps the pool alive until then.
+pub struct Pooled {
+    pool: Arc<Pool>,
+    buf: Option<Buffer<'static>>,
+}
+
+impl Pooled {
+    /// Takes the buffer out of the pool so that it is not returned once dropped.
+    pub fn detach(mut self) -> Buffer<'static> {
+        self.buf.take().unwrap()
+    }
+}
+
+impl Deref for Pooled {
+    type Target = Buffer<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        self.buf.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for Pooled {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.buf.as_mut().unwrap()
+    }
+}
+
+impl Drop for Pooled {
+    fn drop(&mut self) {
+        if let Some(vec) = self.buf.take().and_then(Buffer::into_vec) {
+            self.pool.put(vec);
+        }
+    }
+}
+
+mod tests {
+    ///
+    /// Tests that buffers are reused within their size class and that the pool does not retain
+    /// more bytes than its limit.
+    ///
+    #[test]
+    pub fn pool() {
+        use crate::{Pool, Stats};
+        use std::sync::Arc;
+
+        let pool = Arc::new(Pool::new(100, 1000, 1024));
+
+        let a = pool.get(10);
+        let b = pool.get(300);
+        assert_eq!((a.capacity(), b.capacity()), (128, 512));
+
+        let c = pool.get(2000);
+        assert_eq!(c.capacity(), 2000);
+
+        drop((a, b, c));
+        assert_eq!(pool.stats().retained, 640);
+
+        let mut d = pool.get(500);
+        d.reset();
+        assert_eq!(d.capacity(), 512);
+        drop(d.detach());
+
+        let e = pool.get(1000);
+        let f = pool.get(1000);
+        std::thread::spawn(move || drop((e, f))).join().unwrap();
+
+        let stats = pool.stats();
+        assert_eq!(
+            stats,
+            Stats {
+                hits: 1,
+                misses: 5,
+                retained: 128
+            }
+        );
+    }
+
+    ///
+    /// Tests that sizes beyond the largest power of two do not overflow.
+    ///
+    #[test]
+    pub fn large() {
+        use crate::Pool;
+        use std::sync::Arc;
+
+        let pool = Arc::new(Pool::new(16, usize::MAX, 0));
+        assert_eq!(pool.get(16).capacity(), 16);
+        assert_eq!(pool.stats().misses, 1);
+    }
+}
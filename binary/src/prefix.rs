@@ -1,15 +1,20 @@
-use crate::{Binary, Buffer, ByteOrder, I16, I32, U16, U32, V32, W32};
+use crate::{Binary, Buffer, ByteOrder, I16, I24, I32, U16, U24, U32, V32, W32};
 
 /// Prefix trait is implemented for those integral and numerical types that can serialize the
 /// length of a prefixed datatype such as strings, arrays, etc.
 pub trait Prefix: Binary {
+    /// The largest length the prefix is able to hold. Defaults to no limit.
+    const MAX: usize = usize::MAX;
+
     fn encode(len: usize, buf: &mut Buffer);
     fn decode(buf: &mut Buffer) -> Option<usize>;
 }
 
 macro_rules! impl_prefix {
-    ($wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>, $ty:ty) => {
+    ($wrapper:ident, <$($gen:ident: $gen_constraint:ident),*>, $ty:ty, $max:expr) => {
         impl<'a, $($gen: $gen_constraint),*> Prefix for $wrapper<$($gen),*> {
+            const MAX: usize = $max as usize;
+
             fn encode(prefix: usize, buf: &mut Buffer) {
                 let val = prefix as $ty;
                 Self::new(val).serialize(buf);
@@ -23,9 +28,40 @@ macro_rules! impl_prefix {
     };
 }
 
-impl_prefix!(U16, <E: ByteOrder>, u16);
-impl_prefix!(I16, <E: ByteOrder>, i16);
-impl_prefix!(U32, <E: ByteOrder>, u32);
-impl_prefix!(I32, <E: ByteOrder>, i32);
-impl_prefix!(W32, <>, u32);
-impl_prefix!(V32, <>, i32);
+impl_prefix!(U16, <E: ByteOrder>, u16, u16::MAX);
+impl_prefix!(I16, <E: ByteOrder>, i16, i16::MAX);
+impl_prefix!(U24, <E: ByteOrder>, u32, 0xff_ffff);
+impl_prefix!(I24, <E: ByteOrder>, i32, 0x7f_ffff);
+impl_prefix!(U32, <E: ByteOrder>, u32, u32::MAX);
+impl_prefix!(I32, <E: ByteOrder>, i32, i32::MAX);
+impl_prefix!(W32, <>, u32, u32::MAX);
+impl_prefix!(V32, <>, i32, i32::MAX);
+
+mod tests {
+    ///
+    /// Tests that the 24-bit prefixes encode lengths in their byte order and hold lengths up to
+    /// their maximum.
+    ///
+    #[test]
+    pub fn prefix() {
+        use crate::{Buffer, Prefix, BE, I24, LE, U24};
+
+        assert_eq!(U24::<LE>::MAX, 0xff_ffff);
+        assert_eq!(I24::<BE>::MAX, 0x7f_ffff);
+
+        let mut buf = Buffer::new(12);
+        U24::<LE>::encode(0x01_0203, &mut buf);
+        U24::<BE>::encode(U24::<BE>::MAX, &mut buf);
+        I24::<LE>::encode(5, &mut buf);
+        I24::<BE>::encode(I24::<BE>::MAX, &mut buf);
+        assert_eq!(&buf[..6], &[0x03, 0x02, 0x01, 0xff, 0xff, 0xff]);
+        assert_eq!(&buf[6..], &[0x05, 0x00, 0x00, 0x7f, 0xff, 0xff]);
+
+        buf.set_offset(0);
+        assert_eq!(U24::<LE>::decode(&mut buf), Some(0x01_0203));
+        assert_eq!(U24::<BE>::decode(&mut buf), Some(0xff_ffff));
+        assert_eq!(I24::<LE>::decode(&mut buf), Some(5));
+        assert_eq!(I24::<BE>::decode(&mut buf), Some(0x7f_ffff));
+        assert_eq!(U24::<LE>::decode(&mut buf), None);
+    }
+}
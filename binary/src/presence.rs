@@ -0,0 +1,19 @@
+use crate::{Binary, Bool, Buffer};
+
+/// Presence trait is implemented for those types that can serialize whether an optional value
+/// is present or not, such as the boolean that precedes many optional fields.
+pub trait Presence: Binary {
+    fn encode(present: bool, buf: &mut Buffer);
+    fn decode(buf: &mut Buffer) -> Option<bool>;
+}
+
+impl Presence for Bool {
+    fn encode(present: bool, buf: &mut Buffer) {
+        Self::new(present).serialize(buf);
+    }
+
+    fn decode(buf: &mut Buffer) -> Option<bool> {
+        let val = Self::deserialize(buf)?.get();
+        Some(val)
+    }
+}
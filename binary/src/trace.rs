@@ -0,0 +1,252 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Write;
+
+/// Trace represents a record of the objects deserialized from a [`crate::Buffer`] along with the
+/// byte ranges they were read from and how deeply they were nested. It is recorded once tracing
+/// is enabled on a buffer and can be rendered as an annotated hex dump.
+#[derive(Debug, Default, Clone)]
+pub struct Trace {
+    spans: Vec<Span>,
+    depth: usize,
+    /// The offset of the traced buffer within the buffer tracing was enabled on, which is not
+    /// zero while a view returned by [`crate::Buffer::take`] holds the trace.
+    pub(crate) origin: usize,
+}
+
+/// Span represents a single object deserialized while tracing a buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// The name of the type of the object.
+    pub name: &'static str,
+    /// The offset of the first byte of the object.
+    pub start: usize,
+    /// The offset after the last byte of the object.
+    pub end: usize,
+    /// The number of objects this object was nested in.
+    pub depth: usize,
+    /// Whether the object was deserialized successfully.
+    pub ok: bool,
+}
+
+impl Trace {
+    /// Returns the recorded spans ordered by their start offset and depth.
+    pub fn spans(&self) -> Vec<&Span> {
+        let mut spans = self.spans.iter().collect::<Vec<_>>();
+        spans.sort_by_key(|s| (s.start, s.depth));
+        spans
+    }
+
+    /// Renders the trace as a hex dump of the provided bytes, which are expected to be the bytes
+    /// of the traced buffer, annotated inline with the spans. Every row of 16 bytes is followed by
+    /// the spans starting in it, each marking its bytes within the row and followed by its name.
+    /// Spans that continue past the row end with `..` and spans that failed are marked with `!`.
+    pub fn render(&self, bytes: &[u8]) -> String {
+        let mut out = String::new();
+        let spans = self.spans();
+        let rows = bytes.len().div_ceil(16).max(1);
+
+        for i in 0..rows {
+            let row = &bytes[(i * 16).min(bytes.len())..((i + 1) * 16).min(bytes.len())];
+            _ = write!(out, "{:04x} ", i * 16);
+
+            for b in row.iter() {
+                _ = write!(out, " {:02x}", b);
+            }
+
+            out.push_str(&"   ".repeat(16 - row.len()));
+            out.push_str("  ");
+
+            for b in row.iter() {
+                let c = if b.is_ascii_graphic() || *b == b' ' {
+                    *b as char
+                } else {
+                    '.'
+                };
+                out.push(c);
+            }
+
+            out.push('\n');
+
+            // Spans starting past the bytes, such as those that failed at the end of the buffer,
+            // are annotated below the last row.
+            let start = i * 16;
+            let end = if i + 1 == rows {
+                usize::MAX
+            } else {
+                start + 16
+            };
+
+            for span in spans.iter().filter(|s| s.start >= start && s.start < end) {
+                let col = (span.start - start).min(16);
+                let len = span.end.min(start + 16).saturating_sub(span.start).max(1);
+                let mark = match len {
+                    1 => String::from("^^"),
+                    _ => format!("<{}>", "-".repeat(len * 3 - 3)),
+                };
+
+                _ = write!(out, "      {}{} {}", "   ".repeat(col), mark, span.name);
+                if span.end > start + 16 {
+                    out.push_str(" ..");
+                }
+                if !span.ok {
+                    out.push_str(" !");
+                }
+
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+
+    pub(crate) fn enter(&mut self) -> usize {
+        self.depth += 1;
+        self.depth - 1
+    }
+
+    pub(crate) fn exit(
+        &mut self,
+        name: &'static str,
+        start: usize,
+        end: usize,
+        depth: usize,
+        ok: bool,
+    ) {
+        self.depth = depth;
+        self.spans.push(Span {
+            name,
+            start: start + self.origin,
+            end: end + self.origin,
+            depth,
+            ok,
+        });
+    }
+}
+
+mod tests {
+    ///
+    /// Tests that nested objects are recorded with their byte ranges and depth.
+    ///
+    #[test]
+    pub fn trace() {
+        use crate::{Array, Binary, Buffer, CString, W32};
+
+        let mut buffer = Buffer::from(vec![0x01, 0x02, 0x68, 0x69]);
+        buffer.enable_trace();
+
+        Array::<W32, CString<W32>>::deserialize(&mut buffer).unwrap();
+        let trace = buffer.take_trace().unwrap();
+
+        let spans = trace
+            .spans()
+            .iter()
+            .map(|s| (s.name, s.start, s.end, s.depth))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            spans,
+            [
+                ("Array", 0, 4, 0),
+                ("W32", 0, 1, 1),
+                ("CString", 1, 4, 1),
+                ("W32", 1, 2, 2),
+            ]
+        );
+
+        let render = trace.render(&buffer);
+        let lines = render.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("0000  01 02 68 69"));
+        assert!(lines[0].ends_with("..hi"));
+        assert_eq!(
+            &lines[1..],
+            [
+                "      <---------> Array",
+                "      ^^ W32",
+                "         <------> CString",
+                "         ^^ W32",
+            ]
+        );
+    }
+
+    ///
+    /// Tests that spans continuing past a row and spans that failed are marked as such.
+    ///
+    #[test]
+    pub fn render() {
+        use crate::{Array, Binary, Buffer, CString, U8, W32};
+
+        let mut vec = vec![19];
+        vec.extend(0..19);
+
+        let mut buffer = Buffer::from(vec);
+        buffer.enable_trace();
+        Array::<W32, U8>::deserialize(&mut buffer).unwrap();
+
+        let render = buffer.take_trace().unwrap().render(&buffer);
+        let lines = render.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], format!("      <{}> Array ..", "-".repeat(45)));
+        assert!(lines[3].starts_with("0010  0f 10 11 12"));
+        assert_eq!(lines.len(), 4);
+
+        let mut buffer = Buffer::from(vec![5, 1]);
+        buffer.enable_trace();
+        assert!(CString::<W32>::deserialize(&mut buffer).is_none());
+
+        let render = buffer.take_trace().unwrap().render(&buffer);
+        let lines = render.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1..], ["      ^^ CString !", "      ^^ W32"]);
+    }
+
+    ///
+    /// Tests that objects read through a bounded view are recorded at their offset within the
+    /// buffer the view was taken from.
+    ///
+    #[test]
+    pub fn take() {
+        use crate::{Binary, Buffer, U8};
+
+        let mut buffer = Buffer::from(vec![0x01, 0x02, 0x03, 0x04]);
+        buffer.enable_trace();
+        buffer.advance(1);
+
+        U8::deserialize(&mut buffer.take(2).unwrap().take(1).unwrap()).unwrap();
+        U8::deserialize(&mut buffer).unwrap();
+
+        let trace = buffer.take_trace().unwrap();
+        let spans = trace
+            .spans()
+            .iter()
+            .map(|s| (s.start, s.end))
+            .collect::<Vec<_>>();
+        assert_eq!(spans, [(1, 2), (3, 4)]);
+    }
+
+    ///
+    /// Tests that variable-length integers are recorded the same way whether or not enough bytes
+    /// remain to decode them without bounds checks.
+    ///
+    #[test]
+    pub fn varint() {
+        use crate::{Binary, Buffer, W32};
+
+        let spans = |len: usize| {
+            let mut vec = vec![0u8; len];
+            vec[0] = 0xac;
+            vec[1] = 0x02;
+
+            let mut buffer = Buffer::from(vec);
+            buffer.enable_trace();
+            assert_eq!(W32::deserialize(&mut buffer).unwrap().get(), 300);
+
+            let trace = buffer.take_trace().unwrap();
+            trace
+                .spans()
+                .iter()
+                .map(|s| (s.name, s.start, s.end, s.depth))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(spans(2), [("W32", 0, 2, 0)]);
+        assert_eq!(spans(16), spans(2));
+    }
+}
@@ -0,0 +1,57 @@
+use binary::{Binary, Buffer};
+use nbt::{Encoding, LittleEndian, NetworkLittleEndian, RootNBT};
+use std::time::Instant;
+
+const ROUNDS: u32 = 10;
+
+/// Decodes every block state of the canonical_block_states.nbt fixture, re-encodes them with
+/// each encoding and reports the average time taken to encode and decode all of them. The block
+/// states hold almost no int or long arrays, see the large_arrays benchmark for those.
+fn main() {
+    bench::<NetworkLittleEndian>("NetworkLittleEndian");
+    bench::<LittleEndian>("LittleEndian");
+}
+
+fn bench<E: Encoding>(name: &str) {
+    let bytes = include_bytes!("../src/tests/canonical_block_states.nbt");
+    let mut buffer = Buffer::from(bytes);
+
+    let mut states = Vec::new();
+    while buffer.remaining() != 0 {
+        let nbt = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
+        states.push(RootNBT::<E>::new(nbt.get()));
+    }
+
+    let mut buffer = Buffer::new(bytes.len() * 2);
+    let start = Instant::now();
+
+    for _ in 0..ROUNDS {
+        buffer.reset();
+        for state in states.iter() {
+            state.serialize(&mut buffer);
+        }
+    }
+
+    let encode = start.elapsed() / ROUNDS;
+    let len = buffer.offset();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        buffer.set_offset(0);
+        buffer.resize(len);
+
+        while buffer.remaining() != 0 {
+            RootNBT::<E>::deserialize(&mut buffer).unwrap();
+        }
+    }
+
+    let decode = start.elapsed() / ROUNDS;
+    println!(
+        "{}: {} states, {} bytes, encode {:?}, decode {:?}",
+        name,
+        states.len(),
+        len,
+        encode,
+        decode
+    );
+}
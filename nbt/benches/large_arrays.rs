@@ -0,0 +1,109 @@
+use binary::{Binary, Buffer};
+use engine_api::nbt::{Compound, NBT};
+use nbt::{Encoding, LittleEndian, NetworkLittleEndian, RootNBT};
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 100;
+const LEN: usize = 1 << 16;
+
+/// Encodes and decodes large int and long arrays, the shape of heightmaps and chunk word arrays,
+/// with each encoding. The arrays are written and read once element by element and once through
+/// the bulk array methods of the encoding, and then as part of a whole tree, and the average time
+/// taken for each is reported side by side.
+fn main() {
+    bench::<NetworkLittleEndian>("NetworkLittleEndian");
+    bench::<LittleEndian>("LittleEndian");
+}
+
+fn bench<E: Encoding>(name: &str) {
+    let ints = (0..LEN as i32).map(|v| v * 7919).collect::<Vec<i32>>();
+    let longs = (0..LEN as i64).map(|v| v * 104729).collect::<Vec<i64>>();
+    let mut buffer = Buffer::new(LEN * 16 + 64);
+
+    let element = measure(
+        &mut buffer,
+        |buffer| {
+            for v in ints.iter() {
+                E::write_int(*v, buffer);
+            }
+        },
+        |buffer| {
+            for _ in 0..LEN {
+                E::read_int(buffer).unwrap();
+            }
+        },
+    );
+    let bulk = measure(
+        &mut buffer,
+        |buffer| E::write_int_array(&ints, buffer),
+        |buffer| {
+            E::read_int_array(buffer, LEN).unwrap();
+        },
+    );
+    report(name, "int array", element, bulk);
+
+    let element = measure(
+        &mut buffer,
+        |buffer| {
+            for v in longs.iter() {
+                E::write_long(*v, buffer);
+            }
+        },
+        |buffer| {
+            for _ in 0..LEN {
+                E::read_long(buffer).unwrap();
+            }
+        },
+    );
+    let bulk = measure(
+        &mut buffer,
+        |buffer| E::write_long_array(&longs, buffer),
+        |buffer| {
+            E::read_long_array(buffer, LEN).unwrap();
+        },
+    );
+    report(name, "long array", element, bulk);
+
+    let mut compound = Compound::new();
+    compound.put("ints", NBT::IntArray(ints));
+    compound.put("longs", NBT::LongArray(longs));
+    let root = RootNBT::<E>::new(NBT::Compound(compound));
+
+    let (encode, decode) = measure(
+        &mut buffer,
+        |buffer| root.serialize(buffer),
+        |buffer| {
+            RootNBT::<E>::deserialize(buffer).unwrap();
+        },
+    );
+    println!("{name} tree: encode {encode:?}, decode {decode:?}");
+}
+
+/// Returns the average time taken to encode into and decode from the buffer.
+fn measure(
+    buffer: &mut Buffer,
+    encode: impl Fn(&mut Buffer),
+    decode: impl Fn(&mut Buffer),
+) -> (Duration, Duration) {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        buffer.reset();
+        encode(buffer);
+    }
+    let encoded = start.elapsed() / ROUNDS;
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        buffer.set_offset(0);
+        decode(buffer);
+    }
+
+    (encoded, start.elapsed() / ROUNDS)
+}
+
+fn report(name: &str, kind: &str, element: (Duration, Duration), bulk: (Duration, Duration)) {
+    println!(
+        "{} {} of {} elements: element encode {:?}, decode {:?}; bulk encode {:?}, decode {:?}",
+        name, kind, LEN, element.0, element.1, bulk.0, bulk.1
+    );
+}
@@ -0,0 +1,99 @@
+use crate::{Encoding, RootNBT};
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use engine_api::nbt::{Compound, List, Tag, NBT};
+
+/// The deepest nesting of lists and compounds that is generated.
+const MAX_DEPTH: usize = 8;
+
+/// The largest number of elements generated for a list or a compound.
+const MAX_LEN: usize = 8;
+
+/// The root is always a compound, as it is in every NBT file and packet.
+impl<'a, E: Encoding> Arbitrary<'a> for RootNBT<E> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let val = value::<E>(Tag::Compound, u, MAX_DEPTH)?;
+        Ok(Self::new(val))
+    }
+}
+
+/// Returns an arbitrary tag other than End. Lists and compounds are only returned if the depth
+/// allows for another level of nesting.
+fn tag(u: &mut Unstructured, depth: usize) -> Result<Tag> {
+    let bytes: &[u8] = match depth {
+        0 => &[1, 2, 3, 4, 5, 6, 7, 8, 11, 12],
+        _ => &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+    };
+
+    Ok(Tag::from_byte(*u.choose(bytes)?).unwrap())
+}
+
+/// Returns an arbitrary string that the encoding is able to write.
+fn string<E: Encoding>(u: &mut Unstructured) -> Result<String> {
+    let str = <&str>::arbitrary(u)?;
+
+    let mut len = str.len().min(E::STRING_MAX);
+    while !str.is_char_boundary(len) {
+        len -= 1;
+    }
+
+    Ok(str[..len].to_owned())
+}
+
+/// Returns an arbitrary NBT object with the specified Tag. Every element of a list has the tag of
+/// the list.
+fn value<E: Encoding>(id: Tag, u: &mut Unstructured, depth: usize) -> Result<NBT> {
+    let val = match id {
+        Tag::End => unreachable!("TAG_End has no value"),
+        Tag::Byte => NBT::Byte(u.arbitrary()?),
+        Tag::Short => NBT::Short(u.arbitrary()?),
+        Tag::Int => NBT::Int(u.arbitrary()?),
+        Tag::Long => NBT::Long(u.arbitrary()?),
+        // NaN is replaced with zero as it is not equal to itself, which would prevent comparing
+        // a generated tree with the tree read back from its bytes.
+        Tag::Float => match u.arbitrary::<f32>()? {
+            val if val.is_nan() => NBT::Float(0.0),
+            val => NBT::Float(val),
+        },
+        Tag::Double => match u.arbitrary::<f64>()? {
+            val if val.is_nan() => NBT::Double(0.0),
+            val => NBT::Double(val),
+        },
+        Tag::ByteArray => NBT::ByteArray(u.arbitrary()?),
+        Tag::String => NBT::String(string::<E>(u)?),
+        Tag::List => {
+            let list_type = tag(u, depth - 1)?;
+            let len = u.int_in_range(0..=MAX_LEN)?;
+
+            let mut list = List::with_capacity(list_type, len);
+            for _ in 0..len {
+                list.push(value::<E>(list_type, u, depth - 1)?);
+            }
+
+            NBT::List(list)
+        }
+        Tag::Compound => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+
+            let mut compound = Compound::new();
+            let mut names = Vec::with_capacity(len);
+            for _ in 0..len {
+                let name = string::<E>(u)?;
+                let tag = tag(u, depth - 1)?;
+                let val = value::<E>(tag, u, depth - 1)?;
+
+                // Entries with a name that was already generated are dropped, as only one of them
+                // could be read back.
+                if !names.contains(&name) {
+                    compound.put(&name, val);
+                    names.push(name);
+                }
+            }
+
+            NBT::Compound(compound)
+        }
+        Tag::IntArray => NBT::IntArray(u.arbitrary()?),
+        Tag::LongArray => NBT::LongArray(u.arbitrary()?),
+    };
+
+    Ok(val)
+}
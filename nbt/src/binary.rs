@@ -1,5 +1,5 @@
 use super::Encoding;
-use binary::{generate, Binary, Buffer, U8};
+use binary::{generate, impl_binary_ctx, Binary, Buffer, U8};
 use engine_api::nbt::{Compound, List, Tag, NBT};
 
 // This macro generates the RootNBT object which contains a wrapper around
@@ -13,15 +13,25 @@ impl<E: Encoding> Binary for RootNBT<E> {
         encode::<E>(&self.val, buf);
     }
 
-    fn deserialize(buf: &'a mut Buffer) -> Option<Self> {
-        let tag = deserialize_tag(buf)?;
-        E::read_string(buf)?;
-        let val = decode::<E>(tag, buf)?;
+    fn deserialize(buf: &mut Buffer) -> Option<Self> {
+        buf.trace("RootNBT", |buf| {
+            let tag = deserialize_tag(buf)?;
+            E::read_string(buf)?;
+            let val = decode::<E>(tag, buf)?;
+
+            Some(Self::new(val))
+        })
+    }
 
-        Some(Self::new(val))
+    fn skip(buf: &mut Buffer) -> Option<()> {
+        let tag = deserialize_tag(buf)?;
+        E::skip_string(buf)?;
+        skip::<E>(tag, buf)
     }
 }
 
+impl_binary_ctx!(RootNBT<E: Encoding>);
+
 #[inline]
 fn serialize_tag(tag: Tag, buf: &mut Buffer) {
     let val = tag as u8;
@@ -58,7 +68,7 @@ fn encode<E: Encoding>(nbt: &NBT, buf: &mut Buffer) {
 
             unsafe {
                 let vec: &Vec<u8> = std::mem::transmute(v);
-                buf.write(&vec);
+                buf.write(vec);
             }
         }
         NBT::String(v) => E::write_string(v, buf),
@@ -82,17 +92,11 @@ fn encode<E: Encoding>(nbt: &NBT, buf: &mut Buffer) {
         }
         NBT::IntArray(v) => {
             E::write_int(v.len() as i32, buf);
-
-            for item in v.iter() {
-                E::write_int(*item, buf);
-            }
+            E::write_int_array(v, buf);
         }
         NBT::LongArray(v) => {
             E::write_int(v.len() as i32, buf);
-
-            for item in v.iter() {
-                E::write_long(*item, buf);
-            }
+            E::write_long_array(v, buf);
         }
     }
 }
@@ -100,7 +104,7 @@ fn encode<E: Encoding>(nbt: &NBT, buf: &mut Buffer) {
 /// This function decodes the NBT object with the specified Tag from the buffer and returns it
 /// if successful.
 fn decode<E: Encoding>(id: Tag, buf: &mut Buffer) -> Option<NBT> {
-    match id {
+    buf.trace(tag_name(id), |buf| match id {
         Tag::End => None,
         Tag::Byte => {
             let mut data = [0u8; 1];
@@ -193,25 +197,86 @@ fn decode<E: Encoding>(id: Tag, buf: &mut Buffer) -> Option<NBT> {
         }
         Tag::IntArray => {
             let len = E::read_int(buf)?;
-            let mut array = Vec::with_capacity(len as usize);
-
-            for _ in 0..len {
-                let data = E::read_int(buf)?;
-                array.push(data);
-            }
+            let array = E::read_int_array(buf, len as usize)?;
 
             Some(NBT::IntArray(array))
         }
         Tag::LongArray => {
             let len = E::read_int(buf)?;
-            let mut array = Vec::with_capacity(len as usize);
+            let array = E::read_long_array(buf, len as usize)?;
+
+            Some(NBT::LongArray(array))
+        }
+    })
+}
+
+/// This function advances the buffer past the NBT object with the specified Tag without decoding
+/// it. The lengths of arrays and strings and the ends of compounds are still validated.
+fn skip<E: Encoding>(id: Tag, buf: &mut Buffer) -> Option<()> {
+    match id {
+        Tag::End => None,
+        Tag::Byte => buf.advance(1).then_some(()),
+        Tag::Short => buf.advance(2).then_some(()),
+        Tag::Int => E::skip_int(buf),
+        Tag::Long => E::skip_long(buf),
+        Tag::Float => buf.advance(4).then_some(()),
+        Tag::Double => buf.advance(8).then_some(()),
+        Tag::ByteArray => {
+            let len = E::read_int(buf)? as usize;
+            buf.advance(len).then_some(())
+        }
+        Tag::String => E::skip_string(buf),
+        Tag::List => {
+            let list_type = deserialize_tag(buf)?;
+            let len = E::read_int(buf)?;
+
+            if list_type == Tag::End {
+                return Some(());
+            }
 
             for _ in 0..len {
-                let data = E::read_long(buf)?;
-                array.push(data);
+                skip::<E>(list_type, buf)?;
             }
 
-            Some(NBT::LongArray(array))
+            Some(())
+        }
+        Tag::Compound => loop {
+            let tag = deserialize_tag(buf)?;
+
+            // We encountered the end of a compound tag.
+            if tag == Tag::End {
+                return Some(());
+            }
+
+            E::skip_string(buf)?;
+            skip::<E>(tag, buf)?;
+        },
+        Tag::IntArray => {
+            let len = E::read_int(buf)?;
+            E::skip_int_array(buf, len as usize)
         }
+        Tag::LongArray => {
+            let len = E::read_int(buf)?;
+            E::skip_long_array(buf, len as usize)
+        }
+    }
+}
+
+/// Returns the name of the tag as shown in traces.
+fn tag_name(tag: Tag) -> &'static str {
+    match tag {
+        Tag::End => "TAG_End",
+        Tag::Byte => "TAG_Byte",
+        Tag::Short => "TAG_Short",
+        Tag::Int => "TAG_Int",
+        Tag::Long => "TAG_Long",
+        Tag::Float => "TAG_Float",
+        Tag::Double => "TAG_Double",
+        Tag::ByteArray => "TAG_Byte_Array",
+        Tag::String => "TAG_String",
+        Tag::List => "TAG_List",
+        Tag::Compound => "TAG_Compound",
+        Tag::IntArray => "TAG_Int_Array",
+        Tag::LongArray => "TAG_Long_Array",
     }
 }
@@ -1,18 +1,34 @@
-use binary::{Binary, Buffer, CString, I32, I64, LE, U16, V32, V64, W32};
+use binary::{Binary, Buffer, ByteOrder, CString, Prefix, I32, I64, LE, U16, V32, V64, W32};
 
 /// There are two versions of NBT encoding that is used in Minecraft: Bedrock Edition. The first
 /// one is called the NetworkLittleEndian encoding which is used mostly over the network and the
 /// second encoding is called the LittleEndian encoding which is used for encoding NBT over the
 /// storage and files.
 pub trait Encoding {
+    /// The length of the longest string in bytes that the encoding is able to write. Defaults to
+    /// no limit.
+    const STRING_MAX: usize = usize::MAX;
+
     fn read_int(buf: &mut Buffer) -> Option<i32>;
     fn write_int(val: i32, buf: &mut Buffer);
 
     fn read_long(buf: &mut Buffer) -> Option<i64>;
     fn write_long(val: i64, buf: &mut Buffer);
 
+    fn read_int_array(buf: &mut Buffer, len: usize) -> Option<Vec<i32>>;
+    fn write_int_array(val: &[i32], buf: &mut Buffer);
+
+    fn read_long_array(buf: &mut Buffer, len: usize) -> Option<Vec<i64>>;
+    fn write_long_array(val: &[i64], buf: &mut Buffer);
+
     fn read_string(buf: &mut Buffer) -> Option<String>;
     fn write_string(val: &str, buf: &mut Buffer);
+
+    fn skip_int(buf: &mut Buffer) -> Option<()>;
+    fn skip_long(buf: &mut Buffer) -> Option<()>;
+    fn skip_int_array(buf: &mut Buffer, len: usize) -> Option<()>;
+    fn skip_long_array(buf: &mut Buffer, len: usize) -> Option<()>;
+    fn skip_string(buf: &mut Buffer) -> Option<()>;
 }
 
 /// NetworkLittleEndian encoding is used for encoding NBT objects over the network and the wire. It encodes
@@ -26,6 +42,8 @@ pub struct NetworkLittleEndian;
 pub struct LittleEndian;
 
 impl Encoding for NetworkLittleEndian {
+    const STRING_MAX: usize = W32::MAX;
+
     fn read_int(buf: &mut Buffer) -> Option<i32> {
         let val = V32::deserialize(buf)?.get();
         Some(val)
@@ -44,6 +62,28 @@ impl Encoding for NetworkLittleEndian {
         V64::new(val).serialize(buf);
     }
 
+    fn read_int_array(buf: &mut Buffer, len: usize) -> Option<Vec<i32>> {
+        let vec = V32::deserialize_vec(buf, len)?;
+        Some(vec.into_iter().map(V32::get).collect())
+    }
+
+    fn write_int_array(val: &[i32], buf: &mut Buffer) {
+        for item in val.iter() {
+            V32::new(*item).serialize(buf);
+        }
+    }
+
+    fn read_long_array(buf: &mut Buffer, len: usize) -> Option<Vec<i64>> {
+        let vec = V64::deserialize_vec(buf, len)?;
+        Some(vec.into_iter().map(V64::get).collect())
+    }
+
+    fn write_long_array(val: &[i64], buf: &mut Buffer) {
+        for item in val.iter() {
+            V64::new(*item).serialize(buf);
+        }
+    }
+
     fn read_string(buf: &mut Buffer) -> Option<String> {
         let val = CString::<W32>::deserialize(buf)?.get();
         Some(val)
@@ -53,11 +93,33 @@ impl Encoding for NetworkLittleEndian {
         let len = val.len() as u32;
         W32::new(len).serialize(buf);
 
-        buf.write(&val.as_bytes());
+        buf.write(val.as_bytes());
+    }
+
+    fn skip_int(buf: &mut Buffer) -> Option<()> {
+        V32::skip(buf)
+    }
+
+    fn skip_long(buf: &mut Buffer) -> Option<()> {
+        V64::skip(buf)
+    }
+
+    fn skip_int_array(buf: &mut Buffer, len: usize) -> Option<()> {
+        V32::skip_many(buf, len)
+    }
+
+    fn skip_long_array(buf: &mut Buffer, len: usize) -> Option<()> {
+        V64::skip_many(buf, len)
+    }
+
+    fn skip_string(buf: &mut Buffer) -> Option<()> {
+        CString::<W32>::skip(buf)
     }
 }
 
 impl Encoding for LittleEndian {
+    const STRING_MAX: usize = U16::<LE>::MAX;
+
     fn read_int(buf: &mut Buffer) -> Option<i32> {
         let val = I32::<LE>::deserialize(buf)?.get();
         Some(val)
@@ -76,6 +138,36 @@ impl Encoding for LittleEndian {
         I64::<LE>::new(val).serialize(buf);
     }
 
+    fn read_int_array(buf: &mut Buffer, len: usize) -> Option<Vec<i32>> {
+        if buf.remaining() / 4 < len {
+            return None;
+        }
+
+        let mut vec = vec![0; len];
+        LE::read_i32_slice(buf, &mut vec)?;
+
+        Some(vec)
+    }
+
+    fn write_int_array(val: &[i32], buf: &mut Buffer) {
+        LE::write_i32_slice(val, buf);
+    }
+
+    fn read_long_array(buf: &mut Buffer, len: usize) -> Option<Vec<i64>> {
+        if buf.remaining() / 8 < len {
+            return None;
+        }
+
+        let mut vec = vec![0; len];
+        LE::read_i64_slice(buf, &mut vec)?;
+
+        Some(vec)
+    }
+
+    fn write_long_array(val: &[i64], buf: &mut Buffer) {
+        LE::write_i64_slice(val, buf);
+    }
+
     fn read_string(buf: &mut Buffer) -> Option<String> {
         let val = CString::<U16<LE>>::deserialize(buf)?.get();
         Some(val)
@@ -85,6 +177,26 @@ impl Encoding for LittleEndian {
         let len = val.len() as u16;
         U16::<LE>::new(len).serialize(buf);
 
-        buf.write(&val.as_bytes());
+        buf.write(val.as_bytes());
+    }
+
+    fn skip_int(buf: &mut Buffer) -> Option<()> {
+        I32::<LE>::skip(buf)
+    }
+
+    fn skip_long(buf: &mut Buffer) -> Option<()> {
+        I64::<LE>::skip(buf)
+    }
+
+    fn skip_int_array(buf: &mut Buffer, len: usize) -> Option<()> {
+        I32::<LE>::skip_many(buf, len)
+    }
+
+    fn skip_long_array(buf: &mut Buffer, len: usize) -> Option<()> {
+        I64::<LE>::skip_many(buf, len)
+    }
+
+    fn skip_string(buf: &mut Buffer) -> Option<()> {
+        CString::<U16<LE>>::skip(buf)
     }
 }
@@ -4,4 +4,7 @@ pub use encoding::*;
 pub mod binary;
 pub use binary::*;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
 mod tests;
@@ -5,7 +5,7 @@ pub fn test_biome_definitions() {
     use ::binary::*;
 
     let bytes: &[u8; 25943] = include_bytes!("./biome_definitions.nbt");
-    let mut buffer = Buffer::from(bytes.to_vec());
+    let mut buffer = Buffer::from(bytes);
 
     let nbt = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
     println!("{:?}", nbt);
@@ -18,7 +18,7 @@ pub fn test_biome_definitions_full() {
     use ::binary::*;
 
     let bytes: &[u8; 41676] = include_bytes!("./biome_definitions_full.nbt");
-    let mut buffer = Buffer::from(bytes.to_vec());
+    let mut buffer = Buffer::from(bytes);
 
     let nbt = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
     println!("{:?}", nbt);
@@ -31,7 +31,7 @@ pub fn test_canonical_block_states() {
     use ::binary::*;
 
     let bytes: &[u8; 1987768] = include_bytes!("./canonical_block_states.nbt");
-    let mut buffer = Buffer::from(bytes.to_vec());
+    let mut buffer = Buffer::from(bytes);
 
     while buffer.remaining() != 0 {
         _ = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
@@ -45,7 +45,7 @@ pub fn test_entity_identifiers() {
     use ::binary::*;
 
     let bytes: &[u8; 8173] = include_bytes!("./entity_identifiers.nbt");
-    let mut buffer = Buffer::from(bytes.to_vec());
+    let mut buffer = Buffer::from(bytes);
 
     let nbt = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
     println!("{:?}", nbt);
@@ -58,7 +58,7 @@ pub fn test_crafting_data() {
     use ::binary::*;
 
     let bytes: &[u8; 623733] = include_bytes!("./crafting_data.nbt");
-    let mut buffer = Buffer::from(bytes.to_vec());
+    let mut buffer = Buffer::from(bytes);
 
     let nbt = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
     println!("{:?}", nbt);
@@ -71,7 +71,7 @@ pub fn test_item_runtime_ids() {
     use ::binary::*;
 
     let bytes: &[u8; 44130] = include_bytes!("./item_runtime_ids.nbt");
-    let mut buffer = Buffer::from(bytes.to_vec());
+    let mut buffer = Buffer::from(bytes);
 
     let nbt = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
     println!("{:?}", nbt);
@@ -84,8 +84,66 @@ pub fn test_creative_items() {
     use ::binary::*;
 
     let bytes: &[u8; 96831] = include_bytes!("./creative_items.nbt");
-    let mut buffer = Buffer::from(bytes.to_vec());
+    let mut buffer = Buffer::from(bytes);
 
     let nbt = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
     println!("{:?}", nbt);
 }
+
+/// Tests that skipping the canonical_block_states.nbt file ends on the same offsets as parsing it
+#[test]
+pub fn test_skip() {
+    use crate::*;
+    use ::binary::*;
+
+    let bytes: &[u8; 1987768] = include_bytes!("./canonical_block_states.nbt");
+    let mut buffer = Buffer::from(bytes);
+    let mut skipped = Buffer::from(bytes);
+
+    while buffer.remaining() != 0 {
+        _ = RootNBT::<NetworkLittleEndian>::deserialize(&mut buffer).unwrap();
+        RootNBT::<NetworkLittleEndian>::skip(&mut skipped).unwrap();
+        assert_eq!(buffer.offset(), skipped.offset());
+    }
+
+    let mut truncated = Buffer::from(&bytes[..1000]);
+    while truncated.remaining() != 0 {
+        if RootNBT::<NetworkLittleEndian>::skip(&mut truncated).is_none() {
+            return;
+        }
+    }
+    panic!("skipped a truncated compound");
+}
+
+/// Tests that generated NBT trees round trip through both encodings
+#[cfg(feature = "arbitrary")]
+#[test]
+pub fn test_arbitrary() {
+    use crate::*;
+    use ::arbitrary::{Arbitrary, Unstructured};
+    use ::binary::*;
+
+    fn round_trip<E: Encoding>(data: &[u8]) {
+        let mut u = Unstructured::new(data);
+        while !u.is_empty() {
+            let nbt = RootNBT::<E>::arbitrary(&mut u).unwrap();
+            let mut buffer = Buffer::new(1 << 20);
+            nbt.serialize(&mut buffer);
+            let len = buffer.offset();
+
+            buffer.set_offset(0);
+            let read = RootNBT::<E>::deserialize(&mut buffer).unwrap();
+            assert_eq!(buffer.offset(), len);
+
+            // The trees are compared rather than the bytes as the order of the entries of a
+            // compound may differ.
+            assert_eq!(read, nbt);
+        }
+    }
+
+    let data: Vec<u8> = (0..65536u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+        .collect();
+    round_trip::<NetworkLittleEndian>(&data);
+    round_trip::<LittleEndian>(&data);
+}